@@ -1,53 +1,350 @@
 // src/app.rs
-use std::{error::Error, time::{Duration, Instant}};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs, io,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use chrono::{Days, Local, Months, NaiveDate};
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
 use ratatui::{backend::Backend, Terminal};
 
 use crate::{
-    config::settings::get_editor,
+    config::settings::{
+        active_journal, config, get_editor, get_journal_dir, journal_names, set_active_journal,
+        Keybindings,
+    },
     journal::{
-        commands::{create_new_entry, delete_entry, open_entry},
-        storage::{load_entries, refresh_entries},
+        commands::{delete_entry, open_editor, undo_delete},
+        entry::{JournalEntry, StatefulList},
+        crypto,
+        dates::parse_date,
+        git, history,
+        index::SearchIndex,
+        search::Query,
+        storage::{
+            entry_path, load_content, load_index, new_entry_path, path_for_date, refresh_entries,
+            sort_entries, tag_index,
+        },
+        trash::TrashItem,
+    },
+    ui::{
+        editor_pane::{EditorAction, EditorPane},
+        history_popup::HistoryView,
+        template_picker::TemplatePicker,
+        ui,
     },
-    ui::ui,
 };
 
+pub const CALENDAR_TAB: usize = 1;
+pub const TAGS_TAB: usize = 2;
+const TAB_COUNT: usize = 4;
+
 pub enum InputMode {
     Normal,
     Editing,
 }
 
+/// Which pane of the entries tab takes j/k.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    List,
+    Preview,
+}
+
 pub struct App {
     pub tab_index: usize,
-    pub entries_list: crate::journal::entry::StatefulList<crate::journal::entry::JournalEntry>,
+    pub index: SearchIndex,
+    /// Every entry on disk; `entries_list` holds the ones matching the search
+    pub entries: Vec<JournalEntry>,
+    pub entries_list: StatefulList<JournalEntry>,
     pub input_mode: InputMode,
     pub show_help: bool,
     pub editor: String,
+    pub keys: Keybindings,
     pub status_message: String,
     pub status_time: Option<Instant>,
+    pub calendar_date: NaiveDate,
+    pub search_input: String,
+    pub search: Option<Query>,
+    pub focus: Focus,
+    /// Preview offset per entry id, kept while the app runs
+    pub preview_scrolls: HashMap<String, u16>,
+    /// Rows visible in the preview at the last draw, for paging
+    pub preview_height: u16,
+    /// Show the entry source instead of rendered Markdown
+    pub raw_preview: bool,
+    /// Entry waiting for the delete confirmation popup
+    pub confirm_delete: Option<String>,
+    /// Last entry moved to the trash, restored by `u`
+    pub last_deleted: Option<TrashItem>,
+    /// Revision history popup for the selected entry
+    pub history: Option<HistoryView>,
+    /// Built-in editor, shown in place of the preview while open
+    pub editor_pane: Option<EditorPane>,
+    /// Template choice for a new entry, opened by `n`
+    pub template_picker: Option<TemplatePicker>,
+    /// Text typed into the date prompt opened by `N`
+    pub date_prompt: Option<String>,
+    /// Tags with their entry counts, for the tags tab
+    pub tags: StatefulList<(String, usize)>,
+    /// Only list entries with this tag, picked on the tags tab
+    pub tag_filter: Option<String>,
 }
 
 impl App {
     pub fn new() -> Result<Self, Box<dyn Error>> {
-        let entries = load_entries()?;
+        let index = load_index()?;
+        let mut entries = index.entries();
+        sort_entries(&mut entries);
         let editor = get_editor()?;
 
         Ok(App {
             tab_index: 0,
-            entries_list: crate::journal::entry::StatefulList::with_items(entries),
+            index,
+            entries_list: StatefulList::with_items(entries.clone()),
             input_mode: InputMode::Normal,
             show_help: false,
             editor,
+            keys: config().keybindings,
             status_message: String::new(),
             status_time: None,
+            calendar_date: Local::now().date_naive(),
+            search_input: String::new(),
+            search: None,
+            focus: Focus::List,
+            preview_scrolls: HashMap::new(),
+            preview_height: 0,
+            raw_preview: false,
+            confirm_delete: None,
+            last_deleted: None,
+            history: None,
+            editor_pane: None,
+            template_picker: None,
+            date_prompt: None,
+            tags: StatefulList::with_items(tag_index(&entries)),
+            tag_filter: None,
+            entries,
         })
     }
 
+    pub fn set_entries(&mut self, entries: Vec<JournalEntry>) {
+        self.entries = entries;
+        self.refresh_tags();
+        self.apply_filter();
+    }
+
+    /// Recount the tags, keeping the highlighted one selected.
+    fn refresh_tags(&mut self) {
+        let selected = self.tags.selected_item().map(|(tag, _)| tag.clone());
+        self.tags = StatefulList::with_items(tag_index(&self.entries));
+        if let Some(position) = selected.and_then(|tag| self.tags.items.iter().position(|(t, _)| *t == tag)) {
+            self.tags.state.select(Some(position));
+        }
+    }
+
+    /// Rebuild the visible list from the current search query and tag filter.
+    pub fn apply_filter(&mut self) {
+        let mut items = match &self.search {
+            Some(query) if !query.is_empty() => {
+                // Narrow down with the index, then confirm against the bodies
+                let candidates = self.index.candidates(query);
+                let mut items = Vec::new();
+                for entry in self.entries.iter_mut() {
+                    if candidates.as_ref().is_some_and(|ids| !ids.contains(&entry.id)) {
+                        continue;
+                    }
+                    load_content(entry);
+                    if query.matches_entry(entry) {
+                        items.push(entry.clone());
+                    }
+                }
+                items
+            }
+            _ => self.entries.clone(),
+        };
+        if let Some(tag) = &self.tag_filter {
+            items.retain(|entry| entry.has_tag(tag));
+        }
+        self.entries_list = StatefulList::with_items(items);
+    }
+
+    /// Make sure the highlighted entry has its body loaded for the preview.
+    pub fn load_selected(&mut self) {
+        let Some(selected) = self.entries_list.state.selected() else {
+            return;
+        };
+        if let Some(entry) = self.entries_list.items.get_mut(selected)
+            && entry.content.is_none()
+        {
+            load_content(entry);
+            if let Some(cached) = self.entries.iter_mut().find(|e| e.id == entry.id) {
+                cached.content = entry.content.clone();
+            }
+        }
+    }
+
+    /// Pick up changes to one entry without rescanning the journal, keeping
+    /// the selection on it.
+    pub fn reload_entry(&mut self, id: &str) -> Result<(), Box<dyn Error>> {
+        let previous = self.entries_list.state.selected();
+        let reloaded = self.index.reload(id)?;
+        self.entries.retain(|e| e.id != id);
+        if let Some(entry) = reloaded {
+            self.entries.push(entry);
+            sort_entries(&mut self.entries);
+        }
+        self.refresh_tags();
+        self.apply_filter();
+        let len = self.entries_list.items.len();
+        match self.entries_list.items.iter().position(|e| e.id == id) {
+            Some(position) => self.entries_list.state.select(Some(position)),
+            // Gone (deleted): stay at the same spot in the list
+            None if len > 0 => self.entries_list.state.select(previous.map(|i| i.min(len - 1))),
+            None => {}
+        }
+        Ok(())
+    }
+
+    pub fn preview_scroll(&self) -> u16 {
+        self.entries_list
+            .selected_item()
+            .and_then(|entry| self.preview_scrolls.get(&entry.id))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Set the selected entry's preview offset; the next draw clamps it to the content.
+    pub fn set_preview_scroll(&mut self, scroll: u16) {
+        if let Some(entry) = self.entries_list.selected_item() {
+            self.preview_scrolls.insert(entry.id.clone(), scroll);
+        }
+    }
+
+    pub fn scroll_preview(&mut self, delta: i32) {
+        let scroll = (self.preview_scroll() as i32 + delta).clamp(0, u16::MAX as i32);
+        self.set_preview_scroll(scroll as u16);
+    }
+
+    fn update_search(&mut self) {
+        if self.search_input.trim().is_empty() {
+            self.search = None;
+        } else {
+            match Query::parse(&self.search_input, false, false) {
+                Ok(query) => self.search = Some(query),
+                // Keep filtering by the last valid query while a regex is half typed
+                Err(_) => return,
+            }
+        }
+        self.apply_filter();
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search_input.clear();
+        self.search = None;
+        self.apply_filter();
+    }
+
+    /// List only entries tagged `tag` and switch to the entries tab.
+    pub fn filter_by_tag(&mut self, tag: &str) {
+        self.tag_filter = Some(tag.to_string());
+        self.apply_filter();
+        self.tab_index = 0;
+        self.focus = Focus::List;
+        let count = self.entries_list.items.len();
+        self.set_status(&format!("{} entries tagged #{} (Esc to clear)", count, tag));
+    }
+
+    pub fn clear_tag_filter(&mut self) {
+        self.tag_filter = None;
+        self.apply_filter();
+    }
+
+    /// Cycle to the next configured journal and reload its entries. `unlock`
+    /// is called to ask for the passphrase when that journal is encrypted.
+    pub fn switch_journal(
+        &mut self,
+        unlock: impl FnOnce() -> Result<(), Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let names = journal_names();
+        if names.len() < 2 {
+            self.set_status("No other journals configured (see `notes journals create`)");
+            return Ok(());
+        }
+        let current = active_journal();
+        let position = names.iter().position(|n| *n == current).unwrap_or(0);
+        let next = &names[(position + 1) % names.len()];
+        set_active_journal(next)?;
+        fs::create_dir_all(get_journal_dir())?;
+        if crypto::needs_unlock(&get_journal_dir())
+            && let Err(err) = unlock()
+        {
+            set_active_journal(&current)?;
+            self.set_status(&format!("Can't open journal {}: {}", next, err));
+            return Ok(());
+        }
+
+        self.editor = get_editor()?;
+        self.search_input.clear();
+        self.search = None;
+        self.tag_filter = None;
+        refresh_entries(self)?;
+        self.set_status(&format!("Switched to journal {}", next));
+        Ok(())
+    }
+
+    /// Auto-commit a change when git sync is on; failures go to the status bar.
+    pub fn commit_change(&mut self, message: &str) {
+        if let Err(err) = git::auto_commit(message) {
+            self.set_status(&err.to_string());
+        }
+    }
+
+    /// Pull and push the journal, then pick up whatever came in.
+    pub fn sync(&mut self) -> Result<(), Box<dyn Error>> {
+        self.set_status("Syncing...");
+        match git::sync() {
+            Ok(summary) => {
+                refresh_entries(self)?;
+                self.set_status(&summary);
+            }
+            Err(err) => self.set_status(&err.to_string()),
+        }
+        Ok(())
+    }
+
     pub fn set_status(&mut self, message: &str) {
         self.status_message = message.to_string();
         self.status_time = Some(Instant::now());
     }
+
+    pub fn move_calendar_days(&mut self, days: i64) {
+        let moved = if days >= 0 {
+            self.calendar_date.checked_add_days(Days::new(days as u64))
+        } else {
+            self.calendar_date.checked_sub_days(Days::new(days.unsigned_abs()))
+        };
+        if let Some(date) = moved {
+            self.calendar_date = date;
+        }
+    }
+
+    pub fn move_calendar_months(&mut self, months: i32) {
+        let moved = if months >= 0 {
+            self.calendar_date.checked_add_months(Months::new(months as u32))
+        } else {
+            self.calendar_date.checked_sub_months(Months::new(months.unsigned_abs()))
+        };
+        if let Some(date) = moved {
+            self.calendar_date = date;
+        }
+    }
 }
 
 pub fn run_app<B: Backend>(
@@ -55,68 +352,369 @@ pub fn run_app<B: Backend>(
     app: &mut App,
 ) -> Result<(), Box<dyn Error>> {
     loop {
+        app.load_selected();
         terminal.draw(|f| ui::ui(f, app))?;
 
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match app.input_mode {
-                        InputMode::Normal => match key.code {
-                            KeyCode::Char('q') => return Ok(()),
-                            KeyCode::Char('h') => app.show_help = !app.show_help,
-                            KeyCode::Char('j') | KeyCode::Down => app.entries_list.next(),
-                            KeyCode::Char('k') | KeyCode::Up => app.entries_list.previous(),
-                            KeyCode::Char('n') => {
-                                create_new_entry(app)?;
-                                refresh_entries(app)?;
+        if event::poll(Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            if let Some(pane) = app.editor_pane.as_mut() {
+                match pane.handle_key(key) {
+                    EditorAction::None => {}
+                    EditorAction::Save => save_editor_pane(app)?,
+                    EditorAction::Close => app.editor_pane = None,
+                    EditorAction::Unsaved => {
+                        app.set_status("Unsaved changes: Ctrl-S to save, q again to discard")
+                    }
+                }
+                continue;
+            }
+            if app.history.is_some() {
+                handle_history_key(app, key)?;
+                continue;
+            }
+            if app.template_picker.is_some() {
+                handle_template_key(terminal, app, key)?;
+                continue;
+            }
+            if app.date_prompt.is_some() {
+                handle_date_key(terminal, app, key)?;
+                continue;
+            }
+            if let Some(id) = app.confirm_delete.take() {
+                if matches!(key.code, KeyCode::Char('y') | KeyCode::Enter) {
+                    delete_entry(id, app)?;
+                }
+                continue;
+            }
+            match app.input_mode {
+                InputMode::Normal => {
+                    if app.tab_index == CALENDAR_TAB && handle_calendar_key(terminal, app, key)? {
+                        continue;
+                    }
+                    if app.tab_index == TAGS_TAB && handle_tags_key(app, key) {
+                        continue;
+                    }
+                    let keys = app.keys;
+                    match key.code {
+                        KeyCode::Char(c) if c == keys.quit => return Ok(()),
+                        KeyCode::Char(c) if c == keys.help => app.show_help = !app.show_help,
+                        KeyCode::Char(c) if c == keys.search => {
+                            app.tab_index = 0;
+                            app.input_mode = InputMode::Editing;
+                        }
+                        KeyCode::Char(c) if c == keys.new => match TemplatePicker::open() {
+                            Some(picker) => app.template_picker = Some(picker),
+                            None => create_entry(terminal, app, None)?,
+                        },
+                        KeyCode::Char('N') => app.date_prompt = Some(String::new()),
+                        KeyCode::Enter => {
+                            if let Some(entry) = app.entries_list.selected_item() {
+                                let path = entry_path(&entry.id);
+                                edit_entry(terminal, app, path, None, None)?;
                             }
-                            KeyCode::Enter => {
-                                if let Some(selected) = app.entries_list.state.selected() {
-                                    if !app.entries_list.items.is_empty() {
-                                        let date = app.entries_list.items[selected].date.clone();
-                                        open_entry(&date)?;
-                                        refresh_entries(app)?;
+                        }
+                        KeyCode::Char(c) if c == keys.edit => {
+                            if let Some(entry) = app.entries_list.selected_item() {
+                                match EditorPane::open(entry) {
+                                    Ok(pane) => {
+                                        app.tab_index = 0;
+                                        app.editor_pane = Some(pane);
                                     }
+                                    Err(err) => app.set_status(&format!("Can't open entry: {}", err)),
                                 }
                             }
-                            KeyCode::Char('d') => {
-                                if let Some(selected) = app.entries_list.state.selected() {
-                                    if !app.entries_list.items.is_empty() {
-                                        let date = app.entries_list.items[selected].date.clone();
-                                        delete_entry(date, app)?;
-                                        refresh_entries(app)?;
-                                    }
+                        }
+                        KeyCode::Char(c) if c == keys.delete => {
+                            if let Some(entry) = app.entries_list.selected_item() {
+                                app.confirm_delete = Some(entry.id.clone());
+                            }
+                        }
+                        KeyCode::Char('J') => {
+                            app.switch_journal(|| suspend_tui(terminal, crypto::unlock)?)?
+                        }
+                        KeyCode::Char('u') => undo_delete(app)?,
+                        KeyCode::Char('S') => app.sync()?,
+                        KeyCode::Char('H') => {
+                            if let Some(entry) = app.entries_list.selected_item() {
+                                match HistoryView::open(entry) {
+                                    Ok(view) => app.history = Some(view),
+                                    Err(err) => app.set_status(&format!("Can't read history: {}", err)),
                                 }
                             }
-                            KeyCode::Tab => {
-                                app.tab_index = (app.tab_index + 1) % 3;
+                        }
+                        KeyCode::Char('r') => app.raw_preview = !app.raw_preview,
+                        KeyCode::Char('j') | KeyCode::Down if app.focus == Focus::Preview => {
+                            app.scroll_preview(1)
+                        }
+                        KeyCode::Char('k') | KeyCode::Up if app.focus == Focus::Preview => {
+                            app.scroll_preview(-1)
+                        }
+                        KeyCode::Char('g') if app.focus == Focus::Preview => app.set_preview_scroll(0),
+                        KeyCode::Char('G') if app.focus == Focus::Preview => {
+                            app.set_preview_scroll(u16::MAX)
+                        }
+                        KeyCode::PageDown => app.scroll_preview(app.preview_height.max(1) as i32),
+                        KeyCode::PageUp => app.scroll_preview(-(app.preview_height.max(1) as i32)),
+                        KeyCode::Char('j') | KeyCode::Down => app.entries_list.next(),
+                        KeyCode::Char('k') | KeyCode::Up => app.entries_list.previous(),
+                        KeyCode::Right if app.tab_index == 0 => app.focus = Focus::Preview,
+                        KeyCode::Left if app.tab_index == 0 => app.focus = Focus::List,
+                        KeyCode::Esc if app.search.is_some() => app.clear_search(),
+                        KeyCode::Esc if app.tag_filter.is_some() => app.clear_tag_filter(),
+                        // On the entries tab Tab visits the list, then the preview, then the next tab
+                        KeyCode::Tab => {
+                            if app.tab_index == 0 && app.focus == Focus::List {
+                                app.focus = Focus::Preview;
+                            } else {
+                                app.focus = Focus::List;
+                                app.tab_index = (app.tab_index + 1) % TAB_COUNT;
                             }
-                            KeyCode::BackTab => {
+                        }
+                        KeyCode::BackTab => {
+                            if app.tab_index == 0 && app.focus == Focus::Preview {
+                                app.focus = Focus::List;
+                            } else {
                                 app.tab_index = if app.tab_index > 0 {
                                     app.tab_index - 1
                                 } else {
-                                    2
+                                    TAB_COUNT - 1
                                 };
+                                app.focus = if app.tab_index == 0 { Focus::Preview } else { Focus::List };
                             }
-                            _ => {}
-                        },
-                        InputMode::Editing => match key.code {
-                            KeyCode::Esc => {
-                                app.input_mode = InputMode::Normal;
-                            }
-                            _ => {}
-                        },
+                        }
+                        _ => {}
                     }
                 }
+                InputMode::Editing => match key.code {
+                    KeyCode::Esc => {
+                        app.clear_search();
+                        app.input_mode = InputMode::Normal;
+                    }
+                    KeyCode::Enter => {
+                        app.input_mode = InputMode::Normal;
+                        if app.search.is_some() {
+                            let count = app.entries_list.items.len();
+                            app.set_status(&format!("{} matching entries (Esc to clear)", count));
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        app.search_input.pop();
+                        app.update_search();
+                    }
+                    KeyCode::Char(c) => {
+                        app.search_input.push(c);
+                        app.update_search();
+                    }
+                    _ => {}
+                },
             }
         }
 
         // Clear status message after timeout
-        if let Some(status_time) = app.status_time {
-            if status_time.elapsed() > Duration::from_secs(3) {
-                app.status_message = String::new();
-                app.status_time = None;
+        if let Some(status_time) = app.status_time
+            && status_time.elapsed() > Duration::from_secs(3)
+        {
+            app.status_message = String::new();
+            app.status_time = None;
+        }
+    }
+}
+
+// Calendar navigation: h/j/k/l move the day cursor, [ and ] page months.
+// Returns true when the key was consumed.
+fn handle_calendar_key<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    key: KeyEvent,
+) -> Result<bool, Box<dyn Error>> {
+    match key.code {
+        KeyCode::Char('h') | KeyCode::Left => app.move_calendar_days(-1),
+        KeyCode::Char('l') | KeyCode::Right => app.move_calendar_days(1),
+        KeyCode::Char('k') | KeyCode::Up => app.move_calendar_days(-7),
+        KeyCode::Char('j') | KeyCode::Down => app.move_calendar_days(7),
+        KeyCode::Char('[') => app.move_calendar_months(-1),
+        KeyCode::Char(']') => app.move_calendar_months(1),
+        KeyCode::Char('t') => app.calendar_date = Local::now().date_naive(),
+        KeyCode::Enter => {
+            let path = path_for_date(app.calendar_date)?;
+            let status = format!("Opened entry for {}", app.calendar_date.format("%Y-%m-%d"));
+            edit_entry(terminal, app, path, Some(&status), None)?;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+// Tags tab: j/k move between tags, Enter lists the entries carrying one.
+// Returns true when the key was consumed.
+fn handle_tags_key(app: &mut App, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => app.tags.next(),
+        KeyCode::Char('k') | KeyCode::Up => app.tags.previous(),
+        KeyCode::Enter => {
+            if let Some((tag, _)) = app.tags.selected_item().cloned() {
+                app.filter_by_tag(&tag);
+            }
+        }
+        _ => return false,
+    }
+    true
+}
+
+fn handle_history_key(app: &mut App, key: KeyEvent) -> Result<(), Box<dyn Error>> {
+    let Some(view) = app.history.as_mut() else {
+        return Ok(());
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('H') => app.history = None,
+        KeyCode::Char('j') | KeyCode::Down => view.next(),
+        KeyCode::Char('k') | KeyCode::Up => view.previous(),
+        KeyCode::Char('J') | KeyCode::PageDown => view.scroll_diff(5),
+        KeyCode::Char('K') | KeyCode::PageUp => view.scroll_diff(-5),
+        KeyCode::Enter | KeyCode::Char('r') => {
+            let Some(revision) = view.selected() else {
+                return Ok(());
+            };
+            let saved_at = revision.saved_at;
+            let result = history::restore(revision, &view.path);
+            let id = view.id.clone();
+            app.history = None;
+            match result {
+                Ok(()) => {
+                    app.reload_entry(&id)?;
+                    app.set_status(&format!(
+                        "Restored the version from {} (H to undo)",
+                        saved_at.format("%Y-%m-%d %H:%M:%S")
+                    ));
+                    app.commit_change(&format!("Restore an earlier version of {id}"));
+                }
+                Err(err) => app.set_status(&format!("Can't restore: {}", err)),
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_template_key<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    key: KeyEvent,
+) -> Result<(), Box<dyn Error>> {
+    let Some(picker) = app.template_picker.as_mut() else {
+        return Ok(());
+    };
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => picker.next(),
+        KeyCode::Char('k') | KeyCode::Up => picker.previous(),
+        KeyCode::Enter => {
+            let template = picker.selected().map(str::to_string);
+            app.template_picker = None;
+            create_entry(terminal, app, template.as_deref())?;
+        }
+        KeyCode::Esc | KeyCode::Char('q') => app.template_picker = None,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Typing in the `N` prompt; Enter opens the day's entry, creating it if needed
+fn handle_date_key<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    key: KeyEvent,
+) -> Result<(), Box<dyn Error>> {
+    let Some(input) = app.date_prompt.as_mut() else {
+        return Ok(());
+    };
+    match key.code {
+        KeyCode::Esc => app.date_prompt = None,
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
+        KeyCode::Enter => match parse_date(input, Local::now().date_naive()) {
+            Ok(date) => {
+                app.date_prompt = None;
+                let path = path_for_date(date)?;
+                let status = format!("Opened entry for {}", date.format("%Y-%m-%d"));
+                edit_entry(terminal, app, path, Some(&status), None)?;
+            }
+            // Keep the prompt open so the date can be fixed
+            Err(err) => app.set_status(&err.to_string()),
+        },
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Start a new entry stamped now from `template` (the default when `None`).
+fn create_entry<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    template: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let path = new_entry_path(Local::now().naive_local());
+    edit_entry(terminal, app, path, Some("Created new entry for today"), template)
+}
+
+/// Open `path` in the external editor with the TUI suspended, then reload
+/// just that entry. A new file starts from `template`. Editor problems go to
+/// the status bar rather than ending the session.
+fn edit_entry<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    path: PathBuf,
+    status: Option<&str>,
+    template: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    match suspend_tui(terminal, || open_editor(&path, template))? {
+        Ok(()) => {
+            if let Some(status) = status {
+                app.set_status(status);
             }
         }
+        Err(err) => app.set_status(&err.to_string()),
+    }
+    if let Some(id) = path.file_stem().and_then(|s| s.to_str()) {
+        app.reload_entry(id)?;
+    }
+    Ok(())
+}
+
+/// Give the terminal back to the shell while `run` executes: leave raw mode
+/// and the alternate screen, then restore both and force a full redraw.
+fn suspend_tui<B: Backend, T>(
+    terminal: &mut Terminal<B>,
+    run: impl FnOnce() -> T,
+) -> Result<T, Box<dyn Error>> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+
+    let result = run();
+
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.hide_cursor()?;
+    terminal.clear()?;
+    Ok(result)
+}
+
+fn save_editor_pane(app: &mut App) -> Result<(), Box<dyn Error>> {
+    let Some(pane) = app.editor_pane.as_mut() else {
+        return Ok(());
+    };
+    match pane.save() {
+        Ok(()) => {
+            let id = pane.id.clone();
+            app.reload_entry(&id)?;
+            app.set_status(&format!("Saved {}", id));
+            app.commit_change(&format!("Edit {id}"));
+        }
+        Err(err) => app.set_status(&format!("Can't save: {}", err)),
     }
+    Ok(())
 }
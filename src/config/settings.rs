@@ -1,25 +1,652 @@
 // src/config/settings.rs
-use std::{error::Error, fs, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::{OnceLock, RwLock},
+};
 
+use chrono::format::{Item, StrftimeItems};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Sunday,
+    Monday,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Keybindings {
+    pub quit: char,
+    pub help: char,
+    pub new: char,
+    pub delete: char,
+    pub search: char,
+    pub edit: char,
+}
+
+/// Keys the TUI handles itself, which a configured binding would shadow.
+pub const FIXED_KEYS: [char; 15] =
+    ['j', 'k', 'h', 'l', 'u', 'r', 'g', 'G', 'H', 'J', 'S', 'N', 't', '[', ']'];
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Keybindings {
+            quit: 'q',
+            help: '?',
+            new: 'n',
+            delete: 'd',
+            search: '/',
+            edit: 'e',
+        }
+    }
+}
+
+/// Optional git versioning of the journal directory.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitConfig {
+    /// Turn the journal into a repository and commit every change
+    pub enabled: bool,
+    /// URL added as `origin` when the journal repository has no remote yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+}
+
+/// A named journal with its own directory and optional template/editor.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JournalConfig {
+    pub dir: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+}
+
+/// Name used for the unnamed journal configured by `journal_dir`.
+pub const DEFAULT_JOURNAL: &str = "default";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Command used to open entries
+    pub editor: Option<String>,
+    /// Where entries are stored (defaults to $XDG_DATA_HOME/termjournal/notes)
+    pub journal_dir: Option<PathBuf>,
+    /// chrono format used to display entry dates
+    pub date_format: String,
+    /// Template name from the templates directory used for new entries
+    pub default_template: Option<String>,
+    pub theme: Theme,
+    pub week_start: WeekStart,
+    pub keybindings: Keybindings,
+    /// Journal opened when `--journal` isn't given
+    pub default_journal: Option<String>,
+    /// Days deleted entries stay in `.trash`; 0 keeps them until emptied
+    pub trash_retention_days: u32,
+    pub git: GitConfig,
+    pub journals: BTreeMap<String, JournalConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            editor: None,
+            journal_dir: None,
+            date_format: "%b %d, %Y".to_string(),
+            default_template: None,
+            theme: Theme::default(),
+            week_start: WeekStart::default(),
+            keybindings: Keybindings::default(),
+            default_journal: None,
+            trash_retention_days: 30,
+            git: GitConfig::default(),
+            journals: BTreeMap::new(),
+        }
+    }
+}
+
+/// Keys accepted by `notes config`, with a short description.
+pub const CONFIG_KEYS: [(&str, &str); 16] = [
+    ("editor", "Editor command; {file} and {line} are substituted"),
+    ("journal_dir", "Directory where entries are stored"),
+    ("date_format", "Date display format (chrono strftime)"),
+    ("default_template", "Template used for new entries"),
+    ("theme", "dark or light"),
+    ("week_start", "sunday or monday"),
+    ("default_journal", "Journal used when --journal isn't given"),
+    ("trash_retention_days", "Days before trashed entries are purged (0 = never)"),
+    ("git.enabled", "Commit every change to a git repository in the journal"),
+    ("git.remote", "Remote URL used by `notes sync`"),
+    ("keybindings.quit", "Quit the TUI"),
+    ("keybindings.help", "Toggle help"),
+    ("keybindings.new", "Create a new entry"),
+    ("keybindings.delete", "Delete the selected entry"),
+    ("keybindings.search", "Start a search"),
+    ("keybindings.edit", "Edit the selected entry inside the TUI"),
+];
+
+/// A problem with the config file, naming the offending key.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub key: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            Some(key) => write!(
+                f,
+                "invalid value for `{}` in {}: {}",
+                key,
+                self.path.display(),
+                self.message
+            ),
+            None => write!(f, "invalid config {}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Whether `name` can name a file in the templates directory.
+pub fn is_template_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\'])
+}
+
+impl Config {
+    fn validate(&self, path: &Path) -> Result<(), ConfigError> {
+        let error = |key: &str, message: String| ConfigError {
+            path: path.to_path_buf(),
+            key: Some(key.to_string()),
+            message,
+        };
+
+        if let Some(editor) = &self.editor
+            && editor.trim().is_empty()
+        {
+            return Err(error("editor", "must not be empty".to_string()));
+        }
+        if self.date_format.trim().is_empty()
+            || StrftimeItems::new(&self.date_format).any(|item| matches!(item, Item::Error))
+        {
+            return Err(error(
+                "date_format",
+                format!("`{}` is not a valid date format", self.date_format),
+            ));
+        }
+        if let Some(name) = &self.default_template
+            && !is_template_name(name)
+        {
+            return Err(error("default_template", format!("`{}` is not a template name", name)));
+        }
+
+        if let Some(remote) = &self.git.remote
+            && remote.trim().is_empty()
+        {
+            return Err(error("git.remote", "must not be empty".to_string()));
+        }
+
+        for (name, journal) in &self.journals {
+            let key = format!("journals.{}", name);
+            if let Err(message) = validate_journal_name(name) {
+                return Err(error(&key, message));
+            }
+            if journal.dir.as_os_str().is_empty() {
+                return Err(error(&format!("{key}.dir"), "must not be empty".to_string()));
+            }
+            if let Some(template) = &journal.template
+                && (template.is_empty() || template.contains(['/', '\\']))
+            {
+                return Err(error(
+                    &format!("{key}.template"),
+                    format!("`{}` is not a template name", template),
+                ));
+            }
+        }
+        if let Some(name) = &self.default_journal
+            && name != DEFAULT_JOURNAL
+            && !self.journals.contains_key(name)
+        {
+            return Err(error("default_journal", format!("no journal named `{}`", name)));
+        }
+
+        let keys = self.keybindings;
+        let bindings = [
+            ("keybindings.quit", keys.quit),
+            ("keybindings.help", keys.help),
+            ("keybindings.new", keys.new),
+            ("keybindings.delete", keys.delete),
+            ("keybindings.search", keys.search),
+            ("keybindings.edit", keys.edit),
+        ];
+        for (i, (key, c)) in bindings.iter().enumerate() {
+            if c.is_whitespace() || c.is_control() {
+                return Err(error(key, "must be a printable character".to_string()));
+            }
+            if let Some((other, _)) = bindings[..i].iter().find(|(_, o)| o == c) {
+                return Err(error(key, format!("'{}' is already bound to {}", c, other)));
+            }
+            if FIXED_KEYS.contains(c) {
+                return Err(error(key, format!("'{}' is already used by the TUI", c)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_config(text: &str, path: &Path) -> Result<Config, ConfigError> {
+    let config: Config = toml::from_str(text).map_err(|err| {
+        // toml reports the line/column and key; keep its message as-is
+        ConfigError {
+            path: path.to_path_buf(),
+            key: None,
+            message: err.to_string().trim_end().to_string(),
+        }
+    })?;
+    config.validate(path)?;
+    Ok(config)
+}
+
+const APP_DIR: &str = "termjournal";
+
+/// Paths given on the command line, which win over everything else.
+#[derive(Default)]
+pub struct PathOverrides {
+    pub journal_dir: Option<PathBuf>,
+    pub config_path: Option<PathBuf>,
+}
+
+static OVERRIDES: OnceLock<PathOverrides> = OnceLock::new();
+
+fn overrides() -> &'static PathOverrides {
+    OVERRIDES.get_or_init(PathOverrides::default)
+}
+
+fn home_dir() -> PathBuf {
+    dirs::home_dir().unwrap_or_else(|| PathBuf::from("."))
+}
+
+// Non-empty environment variable as a path
+fn env_path(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+// $XDG_* base directory, falling back to the spec default under $HOME.
+// Relative values are invalid per the spec and ignored.
+fn xdg_dir(var: &str, fallback: &[&str]) -> PathBuf {
+    env_path(var)
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| fallback.iter().fold(home_dir(), |dir, part| dir.join(part)))
+}
+
+fn legacy_config_dir() -> PathBuf {
+    home_dir().join(".termjournal")
+}
+
+fn legacy_journal_dir() -> PathBuf {
+    home_dir().join("Documents").join("notes")
+}
+
+/// Config file location: `--config`, then `$NOTES_CONFIG`, then
+/// `$XDG_CONFIG_HOME/termjournal/config.toml`.
+pub fn get_config_path() -> Result<PathBuf, Box<dyn Error>> {
+    let path = match overrides().config_path.clone().or_else(|| env_path("NOTES_CONFIG")) {
+        Some(path) => expand_home(&path),
+        None => xdg_dir("XDG_CONFIG_HOME", &[".config"]).join(APP_DIR).join("config.toml"),
+    };
+    if let Some(dir) = path.parent()
+        && !dir.as_os_str().is_empty()
+    {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Failed to create config directory {}: {}", dir.display(), err))?;
+    }
+    Ok(path)
+}
+
+/// Directory holding the config file, templates and the search index.
+pub fn get_config_dir() -> Result<PathBuf, Box<dyn Error>> {
+    let path = get_config_path()?;
+    Ok(path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(".")))
+}
+
+/// Move everything from the old `~/.termjournal` directory to the XDG
+/// location, converting the single-line `config.txt` (which only held the
+/// editor) into `config.toml`.
+fn migrate_legacy_config(path: &Path) -> Result<(), Box<dyn Error>> {
+    let legacy_dir = legacy_config_dir();
+    let Some(new_dir) = path.parent() else {
+        return Ok(());
+    };
+    if path.exists() || !legacy_dir.is_dir() || legacy_dir == new_dir {
+        return Ok(());
+    }
+
+    for item in fs::read_dir(&legacy_dir)?.flatten() {
+        let name = item.file_name();
+        if name == "config.txt" {
+            let editor = fs::read_to_string(item.path())?;
+            if !editor.trim().is_empty() && !path.exists() {
+                let mut table = toml::Table::new();
+                table.insert("editor".to_string(), toml::Value::String(editor.trim().to_string()));
+                fs::write(path, toml::to_string_pretty(&table)?)?;
+            }
+            fs::remove_file(item.path())?;
+        } else if !new_dir.join(&name).exists() {
+            fs::rename(item.path(), new_dir.join(&name))?;
+        }
+    }
+    // Only removed if everything moved over
+    let _ = fs::remove_dir(&legacy_dir);
+    eprintln!(
+        "Moved settings from {} to {}",
+        legacy_dir.display(),
+        new_dir.display()
+    );
+    Ok(())
+}
+
+pub fn load_config() -> Result<Config, Box<dyn Error>> {
+    let path = get_config_path()?;
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let text = fs::read_to_string(&path)
+        .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+    Ok(parse_config(&text, &path)?)
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The loaded config. `main` loads it up front so errors are reported
+/// there; if that didn't happen the defaults are used.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(|| load_config().unwrap_or_default())
+}
+
+/// Record command-line path overrides, migrate old settings, load the
+/// config and select the journal to work in.
+pub fn init_config(overrides: PathOverrides, journal: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let explicit = overrides.config_path.is_some() || env_path("NOTES_CONFIG").is_some();
+    let _ = OVERRIDES.set(overrides);
+    if !explicit {
+        migrate_legacy_config(&get_config_path()?)?;
+    }
+    let config = load_config()?;
+    let default_journal = config.default_journal.clone();
+    let _ = CONFIG.set(config);
+
+    if let Some(name) = journal.map(str::to_string).or(default_journal) {
+        set_active_journal(&name)?;
+    }
+    Ok(())
+}
+
+/// Look up a (possibly dotted) key as it would appear in the file.
+pub fn get_config_value(key: &str) -> Result<Option<String>, Box<dyn Error>> {
+    check_key(key)?;
+    let table = toml::Table::try_from(config())?;
+    let mut value = Some(toml::Value::Table(table));
+    for part in key.split('.') {
+        value = value.and_then(|v| v.get(part).cloned());
+    }
+    Ok(value.map(|v| match v {
+        toml::Value::String(s) => s,
+        other => other.to_string(),
+    }))
+}
+
+/// Set a key in the config file, validating the result before writing it.
+/// An empty value removes the key so the default applies again.
+pub fn set_config_value(key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    check_key(key)?;
+    update_config_file(key, |table| {
+        let (parent_keys, leaf) = match key.rsplit_once('.') {
+            Some((parents, leaf)) => (parents.split('.').collect::<Vec<_>>(), leaf),
+            None => (Vec::new(), key),
+        };
+        let mut target = table;
+        for part in parent_keys {
+            target = target
+                .entry(part)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| format!("`{}` is not a table", part))?;
+        }
+        if value.is_empty() {
+            target.remove(leaf);
+        } else {
+            target.insert(leaf.to_string(), typed_value(key, value)?);
+        }
+        Ok(())
+    })
+}
+
+// Values arrive as strings from the command line; numbers and booleans
+// are converted according to the type of the key's default.
+fn typed_value(key: &str, value: &str) -> Result<toml::Value, Box<dyn Error>> {
+    let default = toml::Table::try_from(Config::default())?;
+    let mut current = Some(toml::Value::Table(default));
+    for part in key.split('.') {
+        current = current.and_then(|v| v.get(part).cloned());
+    }
+    Ok(match current {
+        Some(toml::Value::Integer(_)) => toml::Value::Integer(
+            value
+                .parse()
+                .map_err(|_| format!("`{}` expects a number, got `{}`", key, value))?,
+        ),
+        Some(toml::Value::Boolean(_)) => toml::Value::Boolean(
+            value
+                .parse()
+                .map_err(|_| format!("`{}` expects true or false, got `{}`", key, value))?,
+        ),
+        _ => toml::Value::String(value.to_string()),
+    })
+}
+
+/// Add a named journal to the config file.
+pub fn add_journal(name: &str, journal: JournalConfig) -> Result<(), Box<dyn Error>> {
+    validate_journal_name(name)?;
+    if config().journals.contains_key(name) {
+        return Err(format!("a journal named `{}` already exists", name).into());
+    }
+    let key = format!("journals.{}", name);
+    update_config_file(&key, |table| {
+        let journals = table
+            .entry("journals")
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or("`journals` is not a table")?;
+        journals.insert(name.to_string(), toml::Value::try_from(&journal)?);
+        Ok(())
+    })
+}
+
+/// Remove a named journal from the config file. Its files are left alone.
+pub fn remove_journal(name: &str) -> Result<JournalConfig, Box<dyn Error>> {
+    let journal = config()
+        .journals
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("no journal named `{}`", name))?;
+    let key = format!("journals.{}", name);
+    update_config_file(&key, |table| {
+        if let Some(journals) = table.get_mut("journals").and_then(|j| j.as_table_mut()) {
+            journals.remove(name);
+            if journals.is_empty() {
+                table.remove("journals");
+            }
+        }
+        if table.get("default_journal").and_then(|v| v.as_str()) == Some(name) {
+            table.remove("default_journal");
+        }
+        Ok(())
+    })?;
+    Ok(journal)
+}
+
+// Apply `edit` to the raw config table, then validate and save it. Keys
+// the user never set stay unset so defaults can change later.
+fn update_config_file(
+    key: &str,
+    edit: impl FnOnce(&mut toml::Table) -> Result<(), Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    let path = get_config_path()?;
+    let text = fs::read_to_string(&path).unwrap_or_default();
+    let mut table: toml::Table = text.parse().map_err(|err: toml::de::Error| ConfigError {
+        path: path.clone(),
+        key: None,
+        message: err.to_string(),
+    })?;
+    edit(&mut table)?;
+
+    let text = toml::to_string_pretty(&table)?;
+    parse_config(&text, &path).map_err(|mut err| {
+        err.key.get_or_insert_with(|| key.to_string());
+        err.message = err.message.lines().last().unwrap_or_default().to_string();
+        err
+    })?;
+    fs::write(&path, text)?;
+    Ok(())
+}
+
+fn validate_journal_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name == DEFAULT_JOURNAL {
+        return Err(format!("`{}` can't be used as a journal name", name));
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!(
+            "`{}` may only contain letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    Ok(())
+}
+
+fn check_key(key: &str) -> Result<(), Box<dyn Error>> {
+    if CONFIG_KEYS.iter().any(|(k, _)| *k == key) {
+        Ok(())
+    } else {
+        Err(format!("unknown config key `{}` (run `notes config` to list keys)", key).into())
+    }
+}
+
+// Selected named journal; `None` means the default journal
+static ACTIVE_JOURNAL: RwLock<Option<String>> = RwLock::new(None);
+
+/// Switch to `name` (or back to the default journal).
+pub fn set_active_journal(name: &str) -> Result<(), Box<dyn Error>> {
+    let selected = if name == DEFAULT_JOURNAL {
+        None
+    } else if config().journals.contains_key(name) {
+        Some(name.to_string())
+    } else {
+        let mut names = journal_names();
+        names.retain(|n| n != DEFAULT_JOURNAL);
+        let known = if names.is_empty() {
+            "none configured".to_string()
+        } else {
+            names.join(", ")
+        };
+        return Err(format!("no journal named `{}` (known: {})", name, known).into());
+    };
+    if let Ok(mut active) = ACTIVE_JOURNAL.write() {
+        *active = selected;
+    }
+    Ok(())
+}
+
+pub fn active_journal() -> String {
+    ACTIVE_JOURNAL
+        .read()
+        .ok()
+        .and_then(|active| active.clone())
+        .unwrap_or_else(|| DEFAULT_JOURNAL.to_string())
+}
+
+fn active_journal_config() -> Option<JournalConfig> {
+    config().journals.get(&active_journal()).cloned()
+}
+
+/// The default journal followed by every named journal.
+pub fn journal_names() -> Vec<String> {
+    std::iter::once(DEFAULT_JOURNAL.to_string())
+        .chain(config().journals.keys().cloned())
+        .collect()
+}
+
+/// Template for new entries in the active journal.
+pub fn get_template_name() -> Option<String> {
+    active_journal_config()
+        .and_then(|journal| journal.template)
+        .or_else(|| config().default_template.clone())
+}
+
+/// Journal directory: `--journal-dir`, then the active named journal. For
+/// the default journal `$NOTES_DIR`, then `journal_dir` from the config;
+/// otherwise the old `~/Documents/notes` is kept if it exists, and new
+/// installs use `$XDG_DATA_HOME/termjournal/notes`.
 pub fn get_journal_dir() -> PathBuf {
-    let mut dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    dir.push("Documents");
-    dir.push("notes");
-    dir
+    if let Some(dir) = &overrides().journal_dir {
+        return expand_home(dir);
+    }
+    if let Some(journal) = active_journal_config() {
+        return expand_home(&journal.dir);
+    }
+    if let Some(dir) = env_path("NOTES_DIR") {
+        return expand_home(&dir);
+    }
+    if let Some(dir) = &config().journal_dir {
+        return expand_home(dir);
+    }
+    let legacy = legacy_journal_dir();
+    if legacy.is_dir() {
+        return legacy;
+    }
+    xdg_dir("XDG_DATA_HOME", &[".local", "share"]).join(APP_DIR).join("notes")
 }
 
-pub fn get_config_path() -> PathBuf {
-    let mut dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    dir.push(".termjournal");
-    fs::create_dir_all(&dir).expect("Failed to create config directory");
-    dir.push("config.txt");
-    dir
+fn expand_home(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => home_dir().join(rest),
+        Err(_) => path.to_path_buf(),
+    }
 }
 
+/// Editor command: `$VISUAL`, then `$EDITOR`, then the journal's `editor`,
+/// the global `editor` setting and finally nvim.
 pub fn get_editor() -> Result<String, Box<dyn Error>> {
-    let config_path = get_config_path();
-    match fs::read_to_string(config_path) {
-        Ok(editor) if !editor.trim().is_empty() => Ok(editor),
-        _ => Ok("nvim".to_string()), // Default to nvim
-    }
+    let from_env = |var: &str| {
+        std::env::var(var)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+    };
+    let editor = from_env("VISUAL")
+        .or_else(|| from_env("EDITOR"))
+        .or_else(|| active_journal_config().and_then(|journal| journal.editor))
+        .or_else(|| config().editor.clone());
+    Ok(editor.unwrap_or_else(|| "nvim".to_string())) // Default to nvim
 }
@@ -0,0 +1,116 @@
+// src/journal/append.rs
+//
+// Quick capture: adding a bullet to an entry without opening an editor.
+use chrono::NaiveTime;
+
+use crate::journal::frontmatter;
+
+/// A list item stamped with `time`. Extra lines of `text` are indented so
+/// they stay part of the item.
+pub fn bullet(time: NaiveTime, text: &str) -> String {
+    let mut lines = text.trim().lines();
+    let mut item = format!("- {} {}", time.format("%H:%M"), lines.next().unwrap_or_default());
+    for line in lines {
+        item.push('\n');
+        if !line.trim().is_empty() {
+            item.push_str("  ");
+            item.push_str(line);
+        }
+    }
+    item
+}
+
+/// Insert `item` at the end of the section under `section` (any heading level,
+/// case-insensitive), or at the end of the entry. A missing section is added
+/// as a new `##` heading.
+pub fn append_item(content: &str, item: &str, section: Option<&str>) -> String {
+    let (_, body) = frontmatter::split(content);
+    let front = &content[..content.len() - body.len()];
+    let mut lines: Vec<String> = body.lines().map(str::to_string).collect();
+
+    let section = section.map(|s| s.trim().trim_start_matches('#').trim());
+    let range = match section {
+        Some(name) => match find_section(&lines, name) {
+            Some(range) => range,
+            None => {
+                push_block(&mut lines, &format!("## {name}"));
+                lines.len()..lines.len()
+            }
+        },
+        None => 0..lines.len(),
+    };
+
+    // Right after the last non-blank line of the section
+    let at = lines[..range.end]
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(range.start, |i| i + 1)
+        .max(range.start);
+    let after_list = at > 0 && is_list_item(&lines[at - 1]);
+    let mut insert: Vec<String> = Vec::new();
+    if at > 0 && !after_list {
+        insert.push(String::new());
+    }
+    insert.extend(item.lines().map(str::to_string));
+    if at < lines.len() && !lines[at].trim().is_empty() {
+        insert.push(String::new());
+    }
+    lines.splice(at..at, insert);
+
+    let mut body = lines.join("\n");
+    body.push('\n');
+    format!("{front}{body}")
+}
+
+// Lines after the heading named `name` up to the next heading of the same or
+// a higher level
+fn find_section(lines: &[String], name: &str) -> Option<std::ops::Range<usize>> {
+    let headings = headings(lines);
+    let (i, &(start, level)) = headings
+        .iter()
+        .enumerate()
+        .find(|(_, (line, _))| heading_text(&lines[*line]).eq_ignore_ascii_case(name))?;
+    let end = headings[i + 1..]
+        .iter()
+        .find(|(_, l)| *l <= level)
+        .map_or(lines.len(), |(line, _)| *line);
+    Some(start + 1..end)
+}
+
+// Line numbers and levels of the ATX headings outside code fences
+fn headings(lines: &[String]) -> Vec<(usize, usize)> {
+    let mut in_code = false;
+    let mut found = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if !in_code && (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            found.push((i, level));
+        }
+    }
+    found
+}
+
+fn heading_text(line: &str) -> &str {
+    line.trim().trim_start_matches('#').trim_end_matches('#').trim()
+}
+
+fn is_list_item(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    ["- ", "* ", "+ "].iter().any(|p| trimmed.starts_with(p)) || line.starts_with("  ")
+}
+
+// Add `line` at the end as its own paragraph
+fn push_block(lines: &mut Vec<String>, line: &str) {
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(line.to_string());
+}
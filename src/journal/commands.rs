@@ -1,124 +1,356 @@
 // src/journal/commands.rs
-use std::{error::Error, fs::{self, File}, io::{self, Write}, path::PathBuf, process::Command};
+use std::{error::Error, fs::{self, File}, io::{self, Write}, path::{Path, PathBuf}};
 
-use chrono::Local;
+use chrono::{Local, NaiveDate, NaiveDateTime};
 use colored::*;
 
-use crate::{app::App, config::settings::{get_editor, get_journal_dir}};
+use crate::{
+    app::App,
+    config::settings::{
+        active_journal, add_journal, config, get_config_path, get_config_value,
+        get_editor, get_journal_dir, remove_journal, set_config_value,
+        JournalConfig, CONFIG_KEYS, DEFAULT_JOURNAL,
+    },
+    journal::{
+        append, crypto,
+        editor::{run_editor, EditorError},
+        entry::{parse_entry_id, JournalEntry, DATE_FORMAT},
+        export::{export_bundle, select_entries, ExportFilter, ExportFormat},
+        frontmatter, git,
+        history::{self, DiffKind},
+        import::{self, Action, ImportFormat, OnConflict, Planned},
+        index::first_heading,
+        search::Query,
+        site,
+        storage::{
+            entry_path, find_entries, load_content, load_entries, load_index, nearest_days,
+            new_entry_path, path_for_date, read_entry, sort_entries, tag_index, write_entry,
+            write_entry_meta, EntryTarget,
+        },
+        tags, template, trash,
+    },
+};
+
+/// Open `path` in the external editor. A new file is first filled from
+/// `template`, or the journal's default template when that's `None`.
+pub fn open_editor(path: &PathBuf, template: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let created = !path.exists();
+    let template = if created {
+        let timestamp = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(parse_entry_id)
+            .unwrap_or_else(|| Local::now().naive_local());
+        Some(template::render(&template::load_template(template)?, timestamp))
+    } else {
+        // Make sure the version we're about to replace is in the history
+        let _ = history::record_file(path);
+        None
+    };
 
-pub fn open_editor(path: &PathBuf) -> Result<(), Box<dyn Error>> {
-    if !path.exists() {
-        let mut file = File::create(path)?;
-        let template = format!("# Journal Entry: {}\n\n", Local::now().format("%Y-%m-%d"));
-        file.write_all(template.as_bytes())?;
-    }
-    
     let editor = get_editor()?;
-    Command::new(editor)
-        .arg(path)
-        .status()
-        .expect("Failed to open editor");
-    
+    if crypto::is_encrypted() {
+        edit_encrypted(&editor, path, template)?;
+    } else {
+        if let Some(template) = &template {
+            File::create(path)?.write_all(template.as_bytes())?;
+        }
+        if let Err(err) = run_editor(&editor, path) {
+            // Don't leave an untouched template behind when the editor never ran
+            if created && !matches!(err, EditorError::Failed(..)) {
+                let _ = fs::remove_file(path);
+            }
+            return Err(err.into());
+        }
+    }
+
+    let _ = history::record_file(path);
+    let id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let action = if created { "Add" } else { "Edit" };
+    git::auto_commit(&format!("{action} {id}")).map_err(|err| format!("Entry saved, but {err}"))?;
     Ok(())
 }
 
-pub fn create_new_entry(app: &mut App) -> Result<(), Box<dyn Error>> {
-    let date = Local::now().format("%Y-%m-%d").to_string();
-    let path = get_journal_dir().join(format!("{date}.md"));
-    open_editor(&path)?;
-    app.set_status("Created new entry for today");
+// The editor only ever sees a decrypted copy in a private temp file, which is
+// wiped once it's read back. New entries aren't written until the editor
+// exits cleanly or changes the template.
+fn edit_encrypted(editor: &str, path: &Path, template: Option<String>) -> Result<(), Box<dyn Error>> {
+    let original = match template {
+        Some(template) => template,
+        None => read_entry(path)?,
+    };
+    let id = path.file_stem().and_then(|s| s.to_str()).unwrap_or("entry");
+    let temp = crypto::SecureTemp::create(id, &original)?;
+    // An editor that exits with an error may still have saved the file, so
+    // keep what was typed before the copy is wiped
+    let result = match run_editor(editor, &temp.path) {
+        Err(err) if !matches!(err, EditorError::Failed(..)) => return Err(err.into()),
+        result => result,
+    };
+    let edited = temp.read()?;
+    if edited != original || (!path.exists() && result.is_ok()) {
+        write_entry(path, &edited)?;
+    }
+    result?;
     Ok(())
 }
 
-pub fn open_entry(date: &str) -> Result<(), Box<dyn Error>> {
-    let path = get_journal_dir().join(format!("{date}.md"));
-    open_editor(&path)?;
+/// Start a fresh note stamped with `timestamp`, leaving any earlier notes that day untouched.
+pub fn new_entry_at(timestamp: NaiveDateTime, template: Option<&str>) -> Result<PathBuf, Box<dyn Error>> {
+    let path = new_entry_path(timestamp);
+    open_editor(&path, template)?;
+    Ok(path)
+}
+
+/// Append `text` as a timestamped bullet to the entry for `date`, creating it
+/// from the default template if the day has none.
+pub fn add_cli(text: &str, date: NaiveDate, tags: &[String], section: Option<&str>) -> Result<(), Box<dyn Error>> {
+    if text.trim().is_empty() {
+        return Err("Nothing to add: pass the text or pipe it in".into());
+    }
+    let path = path_for_date(date)?;
+    let created = !path.exists();
+    let timestamp = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .and_then(parse_entry_id)
+        .unwrap_or_else(|| Local::now().naive_local());
+    let content = if created {
+        template::render(&template::load_template(None)?, timestamp)
+    } else {
+        let _ = history::record_file(&path);
+        read_entry(&path)?
+    };
+
+    let item = append::bullet(Local::now().time(), text);
+    let mut content = append::append_item(&content, &item, section);
+    if !tags.is_empty() {
+        let mut meta = frontmatter::split(&content).0.unwrap_or_default();
+        for tag in tags.iter().map(|t| t.trim().trim_start_matches('#')).filter(|t| !t.is_empty()) {
+            if !meta.tags.iter().any(|t| tags::same_tag(t, tag)) {
+                meta.tags.push(tag.to_string());
+            }
+        }
+        content = frontmatter::with_front_matter(&content, &meta);
+    }
+    write_entry(&path, &content)?;
+    let _ = history::record_file(&path);
+
+    let id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let action = if created { "Add" } else { "Edit" };
+    println!("{}", format!("✓ Added to {}", id).green());
+    commit_cli(&format!("{action} {id}"));
     Ok(())
 }
 
-pub fn delete_entry(date: String, app: &mut App) -> Result<(), Box<dyn Error>> {
-    let path = get_journal_dir().join(format!("{date}.md"));
+/// Move an entry to the trash, remembering it so `u` can bring it back.
+pub fn delete_entry(id: String, app: &mut App) -> Result<(), Box<dyn Error>> {
+    let path = entry_path(&id);
     if path.exists() {
-        fs::remove_file(&path)?;
-        app.set_status(&format!("Deleted entry for {}", date));
+        let item = trash::move_to_trash(&path)?;
+        app.last_deleted = Some(item);
+        app.reload_entry(&id)?;
+        app.set_status(&format!("Moved {} to the trash (u to undo)", id));
+        app.commit_change(&format!("Delete {id}"));
     }
     Ok(())
 }
 
-pub fn view_entry_cli(date: &str) -> Result<(), Box<dyn Error>> {
-    let path = get_journal_dir().join(format!("{date}.md"));
-    if path.exists() {
-        let contents = fs::read_to_string(&path)?;
+/// Restore the entry removed by the last `delete_entry`.
+pub fn undo_delete(app: &mut App) -> Result<(), Box<dyn Error>> {
+    let Some(item) = app.last_deleted.take() else {
+        app.set_status("Nothing to undo");
+        return Ok(());
+    };
+    match trash::restore(&item) {
+        Ok(_) => {
+            app.reload_entry(&item.id)?;
+            app.set_status(&format!("Restored {}", item.id));
+            app.commit_change(&format!("Restore {}", item.id));
+        }
+        Err(err) => app.set_status(&format!("Can't restore {}: {}", item.id, err)),
+    }
+    Ok(())
+}
+
+// Commit after a CLI change; a failed commit doesn't undo the change itself
+fn commit_cli(message: &str) {
+    if let Err(err) = git::auto_commit(message) {
+        println!("{}", format!("Warning: {}", err).yellow());
+    }
+}
+
+fn print_meta(entry: &JournalEntry) {
+    let meta = &entry.meta;
+    if let Some(title) = &meta.title {
+        println!("{} {}", "Title:".bright_black(), title.bold());
+    }
+    if !meta.tags.is_empty() {
+        let tags: Vec<String> = meta.tags.iter().map(|t| format!("#{t}")).collect();
+        println!("{} {}", "Tags:".bright_black(), tags.join(" ").cyan());
+    }
+    if let Some(category) = &meta.category {
+        println!("{} {}", "Category:".bright_black(), category);
+    }
+    if let Some(mood) = &meta.mood {
+        println!("{} {}", "Mood:".bright_black(), mood);
+    }
+    for (key, _) in &meta.custom {
+        if let Some(value) = meta.get(key) {
+            println!("{} {}", format!("{key}:").bright_black(), value);
+        }
+    }
+}
+
+/// Narrow a date or id down to exactly one entry, explaining to the user
+/// when that isn't possible.
+fn resolve_single_entry(target: &str, action: &str) -> Result<Option<JournalEntry>, Box<dyn Error>> {
+    let target = EntryTarget::parse(target)?;
+    let mut entries = find_entries(&target)?;
+    match entries.len() {
+        0 => {
+            println!("{}", format!("No entry to {action} for {target}.").red());
+            suggest_nearest(&target)?;
+            Ok(None)
+        }
+        1 => Ok(entries.pop()),
+        _ => {
+            println!(
+                "{}",
+                format!("{} entries on {target}; pass an entry id to {action} one:", entries.len())
+                    .yellow()
+            );
+            for entry in entries.iter().rev() {
+                println!("• {} {}", entry.id.bold(), format!("({} bytes)", entry.size).bright_black());
+            }
+            Ok(None)
+        }
+    }
+}
+
+// After a miss, point at the days with entries closest to what was asked for
+fn suggest_nearest(target: &EntryTarget) -> Result<(), Box<dyn Error>> {
+    let days = nearest_days(target.date(), 3)?;
+    if days.is_empty() {
+        return Ok(());
+    }
+    println!("{}", "Nearest entries:".yellow());
+    for (day, count) in days {
+        let count = if count == 1 { "1 entry".to_string() } else { format!("{count} entries") };
+        println!("• {} {}", day.format(DATE_FORMAT).to_string().bold(), format!("({count})").bright_black());
+    }
+    Ok(())
+}
+
+pub fn view_entry_cli(target: &str) -> Result<(), Box<dyn Error>> {
+    let target = EntryTarget::parse(target)?;
+    let mut entries = find_entries(&target)?;
+    if entries.is_empty() {
+        println!("{}", format!("No entry for {target}.").red());
+        suggest_nearest(&target)?;
+        return Ok(());
+    }
+
+    // Oldest first so a day reads top to bottom
+    entries.reverse();
+    for entry in &entries {
         println!(
-            "{}\n{}\n{}",
-            format!("📖 Entry for {date}").bold().green(),
-            "─".repeat(50),
-            contents
+            "{} {}\n{}",
+            format!("📖 Entry for {}", entry.date_label()).bold().green(),
+            format!("[{}]", entry.id).bright_black(),
+            "─".repeat(50)
         );
-    } else {
-        println!("{}", "No entry for this date.".red());
+        print_meta(entry);
+        if !entry.meta.is_empty() {
+            println!();
+        }
+        println!("{}", entry.body());
+    }
+    if entries.len() > 1 {
+        println!("{}", format!("{} entries on {target}", entries.len()).bright_black());
     }
     Ok(())
 }
 
-pub fn delete_entry_cli(date: &str) -> Result<(), Box<dyn Error>> {
-    let path = get_journal_dir().join(format!("{date}.md"));
-    if path.exists() {
+pub fn delete_entry_cli(target: &str) -> Result<(), Box<dyn Error>> {
+    let Some(entry) = resolve_single_entry(target, "delete")? else {
+        return Ok(());
+    };
+
+    println!(
+        "{}",
+        format!("Are you sure you want to delete entry {}? (y/N): ", entry.date_label()).yellow()
+    );
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim().to_lowercase() == "y" {
+        let item = trash::move_to_trash(&entry.path)?;
         println!(
             "{}",
-            format!("Are you sure you want to delete entry for {}? (y/N): ", date).yellow()
+            format!("✓ Entry moved to the trash (restore with `notes trash restore {}`).", item.name)
+                .green()
         );
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        if input.trim().to_lowercase() == "y" {
-            fs::remove_file(&path)?;
-            println!("{}", "✓ Entry deleted.".green());
-        } else {
-            println!("{}", "Deletion cancelled.".blue());
-        }
+        commit_cli(&format!("Delete {}", entry.id));
     } else {
-        println!("{}", "No entry to delete.".red());
+        println!("{}", "Deletion cancelled.".blue());
     }
     Ok(())
 }
 
-pub fn display_entries_list() -> Result<(), Box<dyn Error>> {
-    let journal_dir = get_journal_dir();
-    println!("{}", "📅 Journal Entries".bold().blue());
-    println!("{}", "─".repeat(50));
-    
-    let mut entries = Vec::new();
-    for entry in fs::read_dir(&journal_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        // Only process .md files
-        if path.extension().unwrap_or_default() == "md" {
-            if let Some(name) = path.file_name() {
-                if let Some(name_str) = name.to_str() {
-                    let date = name_str.replace(".md", "");
-                    
-                    // Get file metadata for sorting by date
-                    if let Ok(metadata) = fs::metadata(&path) {
-                        entries.push((date, metadata.len()));
-                    }
-                }
+pub fn meta_entry_cli(target: &str, key: Option<&str>, value: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let Some(entry) = resolve_single_entry(target, "update")? else {
+        return Ok(());
+    };
+
+    match (key, value) {
+        (None, _) => {
+            println!("{}", format!("🏷  Metadata for {}", entry.date_label()).bold().green());
+            println!("{}", "─".repeat(50));
+            if entry.meta.is_empty() {
+                println!("{}", "No front matter.".bright_black());
+            } else {
+                print_meta(&entry);
             }
         }
+        (Some(key), None) => match entry.meta.get(key) {
+            Some(value) => println!("{value}"),
+            None => println!("{}", format!("{key} is not set.").yellow()),
+        },
+        (Some(key), Some(value)) => {
+            let mut meta = entry.meta.clone();
+            meta.set(key, value);
+            let _ = history::record_file(&entry.path);
+            write_entry_meta(&entry, &meta)?;
+            let _ = history::record_file(&entry.path);
+            println!("{}", format!("✓ Updated {key}").green());
+            commit_cli(&format!("Update {} of {}", key, entry.id));
+        }
     }
+    Ok(())
+}
+
+/// List entries, only those carrying any of `tags` when given.
+pub fn display_entries_list(tags: &[String]) -> Result<(), Box<dyn Error>> {
+    println!("{}", "📅 Journal Entries".bold().blue());
+    println!("{}", "─".repeat(50));
     
-    // Sort entries by date (newest first)
-    entries.sort_by(|a, b| b.0.cmp(&a.0));
+    // Entries come back sorted newest first
+    let mut entries = load_entries()?;
+    if !tags.is_empty() {
+        entries.retain(|entry| tags.iter().any(|tag| entry.has_tag(tag.trim_start_matches('#'))));
+    }
     
     if entries.is_empty() {
         println!("{}", "No journal entries found.".yellow());
     } else {
-        // Find the longest date string for alignment
-        let _max_len = entries.iter().map(|(date, _)| date.len()).max().unwrap_or(10);
-        
-        for (date, size) in &entries {
-            let size_str = format!("({} bytes)", size);
-            println!("• {} {}", date.bold(), size_str.bright_black());
+        for entry in &entries {
+            let size_str = format!("({} bytes)", entry.size);
+            println!(
+                "• {} {} {}",
+                entry.date_label().bold(),
+                entry.id.bright_black(),
+                size_str.bright_black()
+            );
         }
         
         println!("\n{} entries found", entries.len());
@@ -126,3 +358,502 @@ pub fn display_entries_list() -> Result<(), Box<dyn Error>> {
     
     Ok(())
 }
+
+pub fn tags_list_cli() -> Result<(), Box<dyn Error>> {
+    let tags = tag_index(&load_entries()?);
+    if tags.is_empty() {
+        println!("{}", "No tags yet. Add `tags: [...]` to the front matter or write #tags in an entry.".yellow());
+        return Ok(());
+    }
+
+    println!("{}", "🏷  Tags".bold().blue());
+    println!("{}", "─".repeat(50));
+    let width = tags.iter().map(|(tag, _)| tag.chars().count()).max().unwrap_or(0);
+    for (tag, count) in &tags {
+        println!("• {} {}", format!("#{tag:<width$}").bold(), count.to_string().bright_black());
+    }
+    println!("\n{} tags", tags.len());
+    Ok(())
+}
+
+/// Replace every tag in `from` with `to` in all entries, front matter and
+/// inline. Renaming onto a tag that's already in use merges them.
+pub fn tags_rename_cli(from: &[String], to: &str) -> Result<(), Box<dyn Error>> {
+    let from: Vec<String> = from.iter().map(|t| t.trim().trim_start_matches('#').to_string()).collect();
+    let to = to.trim().trim_start_matches('#');
+    let valid = |tag: &str| tags::inline_tags(&format!("#{tag}")).first().is_some_and(|t| t == tag);
+    if let Some(bad) = from.iter().map(String::as_str).chain([to]).find(|tag| !valid(tag)) {
+        return Err(format!("`{bad}` is not a valid tag (letters, digits, _, - and /, starting with a letter or _)").into());
+    }
+
+    let mut changed = Vec::new();
+    for mut entry in load_entries()? {
+        if !from.iter().any(|tag| entry.has_tag(tag)) {
+            continue;
+        }
+        load_content(&mut entry);
+        if let Some(updated) = tags::rename(entry.content(), &from, to) {
+            let _ = history::record_file(&entry.path);
+            write_entry(&entry.path, &updated)?;
+            let _ = history::record_file(&entry.path);
+            changed.push(entry.id);
+        }
+    }
+
+    let names: Vec<String> = from.iter().map(|t| format!("#{t}")).collect();
+    if changed.is_empty() {
+        println!("{}", format!("No entries tagged {}.", names.join(", ")).yellow());
+        return Ok(());
+    }
+    let plural = if changed.len() == 1 { "entry" } else { "entries" };
+    println!(
+        "{}",
+        format!("✓ Retagged {} as #{} in {} {}", names.join(", "), to, changed.len(), plural).green()
+    );
+    commit_cli(&format!("Retag {} as #{}", names.join(", "), to));
+    Ok(())
+}
+
+const MAX_CONTEXT_LINES: usize = 5;
+
+pub fn search_cli(query: &str, regex: bool, case_sensitive: bool) -> Result<(), Box<dyn Error>> {
+    let query = Query::parse(query, regex, case_sensitive).map_err(|err| format!("Invalid regex: {}", err))?;
+    if query.is_empty() {
+        println!("{}", "Empty search query.".red());
+        return Ok(());
+    }
+
+    println!("{}", format!("🔍 Results for: {}", query.input).bold().blue());
+    println!("{}", "─".repeat(50));
+
+    let index = load_index()?;
+    let candidates = index.candidates(&query);
+    let mut entries = index.entries();
+    sort_entries(&mut entries);
+
+    let mut matches = Vec::new();
+    for mut entry in entries {
+        if candidates.as_ref().is_some_and(|ids| !ids.contains(&entry.id)) {
+            continue;
+        }
+        load_content(&mut entry);
+        if query.matches_entry(&entry) {
+            matches.push(entry);
+        }
+    }
+
+    if matches.is_empty() {
+        println!("{}", "No matching entries.".yellow());
+        return Ok(());
+    }
+
+    for entry in &matches {
+        println!("• {} {}", entry.date_label().bold(), entry.id.bright_black());
+        let lines = query.context_lines(entry.content());
+        for (number, line) in lines.iter().take(MAX_CONTEXT_LINES) {
+            println!(
+                "  {} {}",
+                format!("{:>4} │", number).bright_black(),
+                highlight_line(&query, line)
+            );
+        }
+        if lines.len() > MAX_CONTEXT_LINES {
+            println!(
+                "  {}",
+                format!("… {} more matching lines", lines.len() - MAX_CONTEXT_LINES).bright_black()
+            );
+        }
+    }
+
+    println!("\n{} entries found", matches.len());
+    Ok(())
+}
+
+fn highlight_line(query: &Query, line: &str) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+    for (start, end) in query.find_ranges(line) {
+        out.push_str(&line[last..start]);
+        out.push_str(&line[start..end].black().on_yellow().to_string());
+        last = end;
+    }
+    out.push_str(&line[last..]);
+    out
+}
+
+pub fn config_cli(key: Option<&str>, value: Option<&str>) -> Result<(), Box<dyn Error>> {
+    match (key, value) {
+        (None, _) => {
+            println!("{}", "⚙  Settings".bold().blue());
+            println!("{}", format!("{}", get_config_path()?.display()).bright_black());
+            println!("{}", "─".repeat(50));
+            for (key, description) in CONFIG_KEYS {
+                let value = get_config_value(key)?.unwrap_or_else(|| "(not set)".to_string());
+                println!("{:<20} {:<20} {}", key.bold(), value, description.bright_black());
+            }
+        }
+        (Some(key), None) => match get_config_value(key)? {
+            Some(value) => println!("{value}"),
+            None => println!("{}", format!("{key} is not set.").yellow()),
+        },
+        (Some(key), Some(value)) => {
+            set_config_value(key, value)?;
+            println!("{}", format!("✓ {key} updated!").green());
+        }
+    }
+    Ok(())
+}
+
+pub fn journals_list_cli() -> Result<(), Box<dyn Error>> {
+    println!("{}", "📚 Journals".bold().blue());
+    println!("{}", "─".repeat(50));
+
+    let active = active_journal();
+    let default = config().default_journal.as_deref().unwrap_or(DEFAULT_JOURNAL);
+    let names = std::iter::once(DEFAULT_JOURNAL).chain(config().journals.keys().map(String::as_str));
+    for name in names {
+        let marker = if name == active { "●".green() } else { "○".bright_black() };
+        let dir = match config().journals.get(name) {
+            Some(journal) => journal.dir.display().to_string(),
+            None if name == active => get_journal_dir().display().to_string(),
+            None => "(journal_dir)".to_string(),
+        };
+        let note = if name == default { " (default)" } else { "" };
+        println!("{} {}{} {}", marker, name.bold(), note.bright_black(), dir.bright_black());
+    }
+    Ok(())
+}
+
+pub fn journals_create_cli(
+    name: &str,
+    dir: Option<PathBuf>,
+    template: Option<String>,
+    editor: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let dir = dir.unwrap_or_else(|| {
+        let base = get_journal_dir();
+        base.parent().map(|p| p.join(name)).unwrap_or_else(|| PathBuf::from(name))
+    });
+    let journal = JournalConfig {
+        dir: dir.clone(),
+        template,
+        editor,
+    };
+    add_journal(name, journal)?;
+    fs::create_dir_all(&dir)?;
+    println!("{}", format!("✓ Created journal {name} in {}", dir.display()).green());
+    Ok(())
+}
+
+pub fn journals_remove_cli(name: &str) -> Result<(), Box<dyn Error>> {
+    let journal = remove_journal(name)?;
+    println!(
+        "{}",
+        format!(
+            "✓ Removed journal {name}. Its entries are still in {}",
+            journal.dir.display()
+        )
+        .green()
+    );
+    Ok(())
+}
+
+pub fn trash_list_cli() -> Result<(), Box<dyn Error>> {
+    let items = trash::list_trash()?;
+    if items.is_empty() {
+        println!("{}", "The trash is empty.".blue());
+        return Ok(());
+    }
+
+    println!("{}", "🗑  Trash".bold().green());
+    println!("{}", "─".repeat(50));
+    for item in &items {
+        let content = read_entry(&item.path).unwrap_or_default();
+        let (meta, body) = frontmatter::split(&content);
+        let title = meta
+            .and_then(|m| m.title)
+            .or_else(|| first_heading(body))
+            .unwrap_or_default();
+        let expires = match item.expires_at() {
+            Some(at) => format!("purged {}", at.format("%Y-%m-%d")),
+            None => "kept until emptied".to_string(),
+        };
+        println!(
+            "• {} {} {}",
+            item.name.bold(),
+            title,
+            format!("(deleted {}, {})", item.deleted_at.format("%Y-%m-%d %H:%M"), expires).bright_black()
+        );
+    }
+    Ok(())
+}
+
+pub fn trash_restore_cli(target: &str) -> Result<(), Box<dyn Error>> {
+    let Some(item) = trash::find_in_trash(target)? else {
+        println!("{}", format!("Nothing named {target} in the trash.").red());
+        return Ok(());
+    };
+    let path = trash::restore(&item)?;
+    println!("{}", format!("✓ Restored {} to {}", item.id, path.display()).green());
+    commit_cli(&format!("Restore {}", item.id));
+    Ok(())
+}
+
+pub fn trash_empty_cli() -> Result<(), Box<dyn Error>> {
+    let count = trash::list_trash()?.len();
+    if count == 0 {
+        println!("{}", "The trash is empty.".blue());
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Permanently delete {count} trashed entries? (y/N): ").yellow()
+    );
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim().to_lowercase() == "y" {
+        let purged = trash::empty_trash()?;
+        println!("{}", format!("✓ Deleted {purged} entries for good.").green());
+    } else {
+        println!("{}", "Cancelled.".blue());
+    }
+    Ok(())
+}
+
+/// List an entry's revisions, or diff/restore one by its number in that list.
+pub fn history_cli(target: &str, diff: Option<usize>, restore: Option<usize>) -> Result<(), Box<dyn Error>> {
+    let Some(entry) = resolve_single_entry(target, "inspect")? else {
+        return Ok(());
+    };
+    let revisions = history::revisions(&entry.id)?;
+    if revisions.is_empty() {
+        println!("{}", "No revisions recorded yet.".blue());
+        return Ok(());
+    }
+    let pick = |n: usize| {
+        n.checked_sub(1)
+            .and_then(|i| revisions.get(i))
+            .ok_or_else(|| format!("no revision {n}; pick 1-{}", revisions.len()))
+    };
+
+    if let Some(n) = restore {
+        let revision = pick(n)?;
+        history::restore(revision, &entry.path)?;
+        println!(
+            "{}",
+            format!("✓ Restored {} to the version from {}", entry.id, revision.saved_at.format("%Y-%m-%d %H:%M:%S")).green()
+        );
+        commit_cli(&format!("Restore an earlier version of {}", entry.id));
+        return Ok(());
+    }
+
+    if let Some(n) = diff {
+        let revision = pick(n)?;
+        let lines = history::diff_lines(&revision.content()?, entry.content());
+        if lines.is_empty() {
+            println!("{}", "Revision matches the current entry.".blue());
+        }
+        for line in lines {
+            match line.kind {
+                DiffKind::Context => println!("  {}", line.text),
+                DiffKind::Added => println!("{}", format!("+ {}", line.text).green()),
+                DiffKind::Removed => println!("{}", format!("- {}", line.text).red()),
+                DiffKind::Separator => println!("{}", line.text.bright_black()),
+            }
+        }
+        return Ok(());
+    }
+
+    println!("{}", format!("🕘 History of {}", entry.date_label()).bold().green());
+    println!("{}", "─".repeat(50));
+    let mut current_seen = false;
+    for (i, revision) in revisions.iter().enumerate() {
+        let content = revision.content().unwrap_or_default();
+        let is_current = !current_seen && content == entry.content();
+        current_seen |= is_current;
+        let note = if is_current { " (current)" } else { "" };
+        println!(
+            "{:>3}  {}  {}{}",
+            i + 1,
+            revision.saved_at.format("%Y-%m-%d %H:%M:%S"),
+            format!("{} bytes", content.len()).bright_black(),
+            note.green()
+        );
+    }
+    println!(
+        "{}",
+        "Use --diff <N> to compare a revision with the entry, --restore <N> to bring it back.".bright_black()
+    );
+    Ok(())
+}
+
+pub fn sync_cli() -> Result<(), Box<dyn Error>> {
+    println!("{}", "🔄 Syncing journal...".blue());
+    let summary = git::sync()?;
+    println!("{}", format!("✓ {}", summary).green());
+    Ok(())
+}
+
+// Ask for a new passphrase twice, or take it from $NOTES_PASSPHRASE
+fn new_passphrase() -> Result<String, Box<dyn Error>> {
+    if let Ok(passphrase) = std::env::var(crypto::PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    let passphrase = crypto::prompt_passphrase("New passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err("the passphrase can't be empty".into());
+    }
+    if crypto::prompt_passphrase("Repeat passphrase: ")? != passphrase {
+        return Err("the passphrases don't match".into());
+    }
+    Ok(passphrase)
+}
+
+pub fn encrypt_cli() -> Result<(), Box<dyn Error>> {
+    if crypto::is_encrypted() {
+        return Err(crypto::CryptoError::AlreadyEncrypted.into());
+    }
+    println!(
+        "{}",
+        format!("Encrypting journal {}. Without the passphrase its entries can't be recovered.", active_journal())
+            .yellow()
+    );
+    if get_journal_dir().join(".git").exists() {
+        println!(
+            "{}",
+            "Entries already committed by git sync stay readable in the repository's history (and on its \
+             remote); only new commits are encrypted."
+                .yellow()
+        );
+    }
+    let passphrase = new_passphrase()?;
+    let count = crypto::encrypt_journal(&passphrase)?;
+    println!("{}", format!("✓ Encrypted {count} entries.").green());
+    commit_cli("Encrypt journal");
+    Ok(())
+}
+
+pub fn decrypt_cli() -> Result<(), Box<dyn Error>> {
+    if !crypto::is_encrypted() {
+        return Err(crypto::CryptoError::NotEncrypted.into());
+    }
+    println!(
+        "{}",
+        format!("Entries of journal {} will be stored as plain text again. Continue? (y/N): ", active_journal())
+            .yellow()
+    );
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim().to_lowercase() != "y" {
+        println!("{}", "Cancelled.".blue());
+        return Ok(());
+    }
+    let count = crypto::decrypt_journal()?;
+    println!("{}", format!("✓ Decrypted {count} entries.").green());
+    commit_cli("Decrypt journal");
+    Ok(())
+}
+
+pub fn passwd_cli() -> Result<(), Box<dyn Error>> {
+    if !crypto::is_encrypted() {
+        return Err(crypto::CryptoError::NotEncrypted.into());
+    }
+    let old = crypto::prompt_passphrase("Current passphrase: ")?;
+    let new = new_passphrase()?;
+    crypto::change_passphrase(&old, &new)?;
+    println!("{}", "✓ Passphrase changed.".green());
+    commit_cli("Change journal passphrase");
+    Ok(())
+}
+
+pub fn export_html_cli(out_dir: &Path, filter: &ExportFilter) -> Result<(), Box<dyn Error>> {
+    let entries = select_entries(filter)?;
+    if crypto::is_encrypted() {
+        println!("{}", "Warning: this journal is encrypted; the exported pages are not.".yellow());
+    }
+    let tags = site::export_site(&entries, out_dir)?;
+    println!(
+        "{}",
+        format!("✓ Exported {} entries and {} tag pages to {}", entries.len(), tags, out_dir.display()).green()
+    );
+    println!("Open {} in a browser.", out_dir.join("index.html").display());
+    Ok(())
+}
+
+/// Write the selected entries as one document to `output`, or stdout.
+pub fn export_bundle_cli(
+    format: ExportFormat,
+    output: Option<&Path>,
+    filter: &ExportFilter,
+) -> Result<(), Box<dyn Error>> {
+    let entries = select_entries(filter)?;
+    let bundle = export_bundle(&entries, format)?;
+    match output {
+        Some(path) => {
+            if crypto::is_encrypted() {
+                println!("{}", "Warning: this journal is encrypted; the export is not.".yellow());
+            }
+            fs::write(path, bundle)?;
+            println!(
+                "{}",
+                format!("✓ Exported {} entries to {}", entries.len(), path.display()).green()
+            );
+        }
+        None => io::stdout().write_all(bundle.as_bytes())?,
+    }
+    Ok(())
+}
+
+pub fn import_cli(
+    format: ImportFormat,
+    source: &Path,
+    on_conflict: OnConflict,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let imported = format.importer().read(source)?;
+    if imported.is_empty() {
+        println!("{}", format!("No entries found in {}.", source.display()).yellow());
+        return Ok(());
+    }
+    let planned = import::plan(imported, on_conflict)?;
+
+    if dry_run {
+        println!("{}", "Dry run; nothing will be written.".blue());
+    }
+    let (mut created, mut merged, mut skipped) = (0, 0, 0);
+    for Planned { entry, action } in &planned {
+        let title = entry.meta.title.clone().unwrap_or_else(|| entry.source.clone());
+        match action {
+            Action::Create(path) => {
+                created += 1;
+                let id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+                println!("{} {} {}", "+".green(), id.bold(), title);
+            }
+            Action::Merge(path) => {
+                merged += 1;
+                let id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+                println!("{} {} {}", "~".yellow(), format!("into {id}").bold(), title);
+            }
+            Action::Skip(id) => {
+                skipped += 1;
+                println!(
+                    "{} {} {}",
+                    "-".bright_black(),
+                    entry.source.bright_black(),
+                    format!("(skipped, {id} exists)").bright_black()
+                );
+            }
+        }
+    }
+
+    let summary = format!("{created} new, {merged} merged, {skipped} skipped");
+    if dry_run {
+        println!("\nWould import: {}", summary);
+        return Ok(());
+    }
+    import::apply(&planned)?;
+    println!("{}", format!("✓ Imported: {}", summary).green());
+    commit_cli(&format!("Import {} entries", created + merged));
+    Ok(())
+}
@@ -0,0 +1,477 @@
+// src/journal/crypto.rs
+//
+// Encrypted journals. A journal is encrypted when its directory holds an
+// `.encryption.json` key file. Entries are then stored as
+//
+//     termjournal-encrypted-v1
+//     <base64 of nonce + XChaCha20-Poly1305 ciphertext>
+//
+// under a random data key. The key file keeps that data key wrapped with a
+// key derived from the passphrase (Argon2id), so changing the passphrase only
+// rewraps the data key instead of re-encrypting every entry.
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::settings::{active_journal, get_journal_dir},
+    journal::index::index_path,
+};
+
+const MAGIC: &str = "termjournal-encrypted-v1\n";
+const KEY_FILE: &str = ".encryption.json";
+const NONCE_LEN: usize = 24;
+const SALT_LEN: usize = 16;
+/// Lets scripts supply the passphrase without a prompt
+pub const PASSPHRASE_ENV: &str = "NOTES_PASSPHRASE";
+
+type Key = [u8; 32];
+
+#[derive(Debug)]
+pub enum CryptoError {
+    /// The passphrase didn't unwrap the data key
+    WrongPassphrase,
+    /// Data was tampered with or encrypted under another key
+    Corrupt,
+    NotEncrypted,
+    AlreadyEncrypted,
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::WrongPassphrase => write!(f, "wrong passphrase"),
+            CryptoError::Corrupt => write!(f, "can't decrypt: data is damaged or was encrypted with another key"),
+            CryptoError::NotEncrypted => write!(f, "this journal isn't encrypted"),
+            CryptoError::AlreadyEncrypted => write!(f, "this journal is already encrypted"),
+        }
+    }
+}
+
+impl Error for CryptoError {}
+
+#[derive(Serialize, Deserialize)]
+struct KeyFile {
+    version: u32,
+    /// Argon2id parameters
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    /// Data key sealed with the passphrase key
+    wrapped_key: String,
+}
+
+// Unlocked data keys per journal directory, for the life of the process
+fn keys() -> &'static Mutex<HashMap<PathBuf, Key>> {
+    static KEYS: OnceLock<Mutex<HashMap<PathBuf, Key>>> = OnceLock::new();
+    KEYS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn key_file_path(dir: &Path) -> PathBuf {
+    dir.join(KEY_FILE)
+}
+
+/// Whether the active journal is encrypted.
+pub fn is_encrypted() -> bool {
+    is_encrypted_dir(&get_journal_dir())
+}
+
+pub fn is_encrypted_dir(dir: &Path) -> bool {
+    key_file_path(dir).exists()
+}
+
+/// True if `dir` is encrypted and its key hasn't been unlocked yet.
+pub fn needs_unlock(dir: &Path) -> bool {
+    is_encrypted_dir(dir) && !keys().lock().unwrap().contains_key(dir)
+}
+
+/// Unlock the active journal, asking for the passphrase if it isn't in
+/// `$NOTES_PASSPHRASE`. A no-op for plaintext journals.
+pub fn unlock() -> Result<(), Box<dyn Error>> {
+    let dir = get_journal_dir();
+    if !needs_unlock(&dir) {
+        return Ok(());
+    }
+    let passphrase = match env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) => passphrase,
+        Err(_) => prompt_passphrase(&format!("Passphrase for journal {}: ", active_journal()))?,
+    };
+    let key = unwrap_key(&read_key_file(&dir)?, &passphrase)?;
+    keys().lock().unwrap().insert(dir, key);
+    Ok(())
+}
+
+fn key() -> Result<Key, Box<dyn Error>> {
+    unlock()?;
+    Ok(keys().lock().unwrap()[&get_journal_dir()])
+}
+
+/// Read a passphrase from the terminal without echoing it.
+pub fn prompt_passphrase(prompt: &str) -> io::Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let was_raw = is_raw_mode_enabled()?;
+    enable_raw_mode()?;
+    let mut passphrase = String::new();
+    let result = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Backspace => {
+                    passphrase.pop();
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled"));
+                }
+                KeyCode::Char(c) => passphrase.push(c),
+                KeyCode::Esc => break Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled")),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(err) => break Err(err),
+        }
+    };
+    if !was_raw {
+        disable_raw_mode()?;
+    }
+    println!();
+    result.map(|()| passphrase)
+}
+
+fn read_key_file(dir: &Path) -> Result<KeyFile, Box<dyn Error>> {
+    let path = key_file_path(dir);
+    let data = fs::read_to_string(&path)?;
+    serde_json::from_str(&data).map_err(|err| format!("{}: {}", path.display(), err).into())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], params: Params) -> Result<Key, Box<dyn Error>> {
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| format!("key derivation failed: {}", err))?;
+    Ok(key)
+}
+
+fn wrap_key(data_key: &Key, passphrase: &str) -> Result<KeyFile, Box<dyn Error>> {
+    let params = Params::default();
+    let salt: [u8; SALT_LEN] = XChaCha20Poly1305::generate_nonce(&mut OsRng)[..SALT_LEN]
+        .try_into()
+        .expect("nonce is longer than the salt");
+    let passphrase_key = derive_key(passphrase, &salt, params.clone())?;
+    Ok(KeyFile {
+        version: 1,
+        salt: STANDARD.encode(salt),
+        memory_kib: params.m_cost(),
+        iterations: params.t_cost(),
+        parallelism: params.p_cost(),
+        wrapped_key: STANDARD.encode(seal(&passphrase_key, data_key)),
+    })
+}
+
+fn unwrap_key(file: &KeyFile, passphrase: &str) -> Result<Key, Box<dyn Error>> {
+    let salt = STANDARD.decode(&file.salt)?;
+    let params = Params::new(file.memory_kib, file.iterations, file.parallelism, Some(32))
+        .map_err(|err| format!("bad key file parameters: {}", err))?;
+    let passphrase_key = derive_key(passphrase, &salt, params)?;
+    let wrapped = STANDARD.decode(&file.wrapped_key)?;
+    let key = open(&passphrase_key, &wrapped).map_err(|_| CryptoError::WrongPassphrase)?;
+    key.try_into().map_err(|_| CryptoError::Corrupt.into())
+}
+
+fn seal(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let mut sealed = nonce.to_vec();
+    sealed.extend(cipher.encrypt(&nonce, plaintext).expect("encryption can't fail"));
+    sealed
+}
+
+fn open(key: &Key, sealed: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if sealed.len() < NONCE_LEN {
+        return Err(CryptoError::Corrupt);
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(key.into())
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptoError::Corrupt)
+}
+
+/// Whether `data` is an encrypted entry.
+pub fn is_sealed(data: &str) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// Encrypt entry text for the active journal.
+pub fn encrypt_text(text: &str) -> Result<String, Box<dyn Error>> {
+    Ok(format!("{}{}\n", MAGIC, STANDARD.encode(seal(&key()?, text.as_bytes()))))
+}
+
+/// Decrypt entry text; plaintext is passed through unchanged.
+pub fn decrypt_text(data: &str) -> Result<String, Box<dyn Error>> {
+    let Some(encoded) = data.strip_prefix(MAGIC) else {
+        return Ok(data.to_string());
+    };
+    let sealed = STANDARD.decode(encoded.trim()).map_err(|_| CryptoError::Corrupt)?;
+    Ok(String::from_utf8(open(&key()?, &sealed)?)?)
+}
+
+/// Encrypt binary data (history revisions) for the active journal.
+pub fn encrypt_bytes(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    Ok(seal(&key()?, data))
+}
+
+pub fn decrypt_bytes(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    Ok(open(&key()?, data)?)
+}
+
+/// Entry files, trashed entries included, in the active journal.
+fn entry_files(dir: &Path) -> Vec<PathBuf> {
+    [dir.to_path_buf(), dir.join(".trash")]
+        .iter()
+        .filter_map(|d| fs::read_dir(d).ok())
+        .flat_map(|files| files.flatten().map(|f| f.path()))
+        .filter(|path| path.extension().unwrap_or_default() == "md")
+        .collect()
+}
+
+// Gzipped revisions under `.history`; sealed ones lose the gzip magic
+fn revision_files(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir.join(".history"))
+        .into_iter()
+        .flat_map(|ids| ids.flatten())
+        .filter_map(|id| fs::read_dir(id.path()).ok())
+        .flat_map(|files| files.flatten().map(|f| f.path()))
+        .collect()
+}
+
+fn is_gzip(data: &[u8]) -> bool {
+    data.starts_with(&[0x1f, 0x8b])
+}
+
+/// Encrypt every entry of the active journal under a new passphrase.
+/// Returns how many files were encrypted.
+pub fn encrypt_journal(passphrase: &str) -> Result<usize, Box<dyn Error>> {
+    let dir = get_journal_dir();
+    if is_encrypted_dir(&dir) {
+        return Err(CryptoError::AlreadyEncrypted.into());
+    }
+    let data_key: Key = XChaCha20Poly1305::generate_key(&mut OsRng).into();
+    let key_file = wrap_key(&data_key, passphrase)?;
+    fs::write(key_file_path(&dir), serde_json::to_string_pretty(&key_file)?)?;
+    keys().lock().unwrap().insert(dir.clone(), data_key);
+
+    let mut count = 0;
+    for path in entry_files(&dir) {
+        let content = fs::read_to_string(&path)?;
+        if !is_sealed(&content) {
+            fs::write(&path, encrypt_text(&content)?)?;
+            count += 1;
+        }
+    }
+    for path in revision_files(&dir) {
+        let data = fs::read(&path)?;
+        if is_gzip(&data) {
+            fs::write(&path, encrypt_bytes(&data)?)?;
+        }
+    }
+    // The on-disk index holds entry words; it's kept in memory from now on
+    let _ = fs::remove_file(index_path()?);
+    Ok(count)
+}
+
+/// Decrypt every entry of the active journal and drop its key file.
+pub fn decrypt_journal() -> Result<usize, Box<dyn Error>> {
+    let dir = get_journal_dir();
+    if !is_encrypted_dir(&dir) {
+        return Err(CryptoError::NotEncrypted.into());
+    }
+    unlock()?;
+    let mut count = 0;
+    for path in entry_files(&dir) {
+        let content = fs::read_to_string(&path)?;
+        if is_sealed(&content) {
+            fs::write(&path, decrypt_text(&content)?)?;
+            count += 1;
+        }
+    }
+    for path in revision_files(&dir) {
+        let data = fs::read(&path)?;
+        if !is_gzip(&data) {
+            fs::write(&path, decrypt_bytes(&data)?)?;
+        }
+    }
+    fs::remove_file(key_file_path(&dir))?;
+    keys().lock().unwrap().remove(&dir);
+    Ok(count)
+}
+
+/// Rewrap the data key of the active journal under `new` after checking `old`.
+pub fn change_passphrase(old: &str, new: &str) -> Result<(), Box<dyn Error>> {
+    let dir = get_journal_dir();
+    if !is_encrypted_dir(&dir) {
+        return Err(CryptoError::NotEncrypted.into());
+    }
+    let data_key = rewrap_key_file(&dir, old, new)?;
+    keys().lock().unwrap().insert(dir, data_key);
+    Ok(())
+}
+
+// Replace the key file in `dir` with one for `new`, returning the data key
+fn rewrap_key_file(dir: &Path, old: &str, new: &str) -> Result<Key, Box<dyn Error>> {
+    let data_key = unwrap_key(&read_key_file(dir)?, old)?;
+    let key_file = wrap_key(&data_key, new)?;
+    let path = key_file_path(dir);
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(&key_file)?)?;
+    fs::rename(tmp, path)?;
+    Ok(data_key)
+}
+
+/// Decrypted copy of an entry for an external editor: readable only by the
+/// user, kept in memory-backed storage when available, and overwritten
+/// before removal when dropped.
+pub struct SecureTemp {
+    pub path: PathBuf,
+}
+
+impl SecureTemp {
+    pub fn create(id: &str, content: &str) -> io::Result<SecureTemp> {
+        let shm = Path::new("/dev/shm");
+        let base = if shm.is_dir() { shm.to_path_buf() } else { env::temp_dir() };
+        // Unguessable and created fresh, so nobody can plant the directory first
+        let dir = base.join(format!("termjournal-{}-{:016x}", std::process::id(), OsRng.next_u64()));
+        create_private_dir(&dir)?;
+        let path = dir.join(format!("{id}.md"));
+        let mut file = create_private_file(&path)?;
+        file.write_all(content.as_bytes())?;
+        Ok(SecureTemp { path })
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        fs::read_to_string(&self.path)
+    }
+}
+
+impl Drop for SecureTemp {
+    fn drop(&mut self) {
+        if let Ok(metadata) = fs::metadata(&self.path) {
+            let _ = fs::write(&self.path, vec![0u8; metadata.len() as usize]);
+        }
+        let _ = fs::remove_file(&self.path);
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+}
+
+// Both fail if `dir` already exists
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    fs::DirBuilder::new().mode(0o700).create(dir)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    fs::create_dir(dir)
+}
+
+#[cfg(unix)]
+fn create_private_file(path: &Path) -> io::Result<fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    fs::OpenOptions::new().write(true).create_new(true).mode(0o600).open(path)
+}
+
+#[cfg(not(unix))]
+fn create_private_file(path: &Path) -> io::Result<fs::File> {
+    fs::OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_key() -> Key {
+        XChaCha20Poly1305::generate_key(&mut OsRng).into()
+    }
+
+    fn is_wrong_passphrase(err: &(dyn Error + 'static)) -> bool {
+        matches!(err.downcast_ref::<CryptoError>(), Some(CryptoError::WrongPassphrase))
+    }
+
+    #[test]
+    fn seal_round_trip() {
+        let key = data_key();
+        let sealed = seal(&key, b"dear diary");
+        assert_ne!(&sealed[NONCE_LEN..], b"dear diary");
+        assert_eq!(open(&key, &sealed).unwrap(), b"dear diary");
+        // Fresh nonce every time
+        assert_ne!(seal(&key, b"dear diary"), sealed);
+        assert!(matches!(open(&data_key(), &sealed), Err(CryptoError::Corrupt)));
+    }
+
+    #[test]
+    fn wrapped_key_round_trip() {
+        let key = data_key();
+        let file = wrap_key(&key, "correct horse").unwrap();
+        assert_eq!(unwrap_key(&file, "correct horse").unwrap(), key);
+        let err = unwrap_key(&file, "battery staple").unwrap_err();
+        assert!(is_wrong_passphrase(err.as_ref()), "{err}");
+    }
+
+    #[test]
+    fn change_passphrase_rewraps_key() {
+        let dir = env::temp_dir().join(format!("termjournal-crypto-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let key = data_key();
+        let file = wrap_key(&key, "old").unwrap();
+        fs::write(key_file_path(&dir), serde_json::to_string(&file).unwrap()).unwrap();
+
+        let err = rewrap_key_file(&dir, "wrong", "new").unwrap_err();
+        assert!(is_wrong_passphrase(err.as_ref()), "{err}");
+        assert_eq!(rewrap_key_file(&dir, "old", "new").unwrap(), key);
+        let file = read_key_file(&dir).unwrap();
+        assert_eq!(unwrap_key(&file, "new").unwrap(), key);
+        assert!(is_wrong_passphrase(unwrap_key(&file, "old").unwrap_err().as_ref()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn secure_temp_is_private_and_removed() {
+        let first = SecureTemp::create("entry", "secret").unwrap();
+        let second = SecureTemp::create("entry", "other").unwrap();
+        let dir = first.path.parent().unwrap().to_path_buf();
+        assert_ne!(Some(dir.as_path()), second.path.parent());
+        assert_eq!(first.read().unwrap(), "secret");
+        // An existing directory is never reused
+        assert_eq!(create_private_dir(&dir).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&dir).unwrap().permissions().mode() & 0o777, 0o700);
+            assert_eq!(fs::metadata(&first.path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        drop(first);
+        assert!(!dir.exists());
+    }
+}
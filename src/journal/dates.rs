@@ -0,0 +1,153 @@
+// src/journal/dates.rs
+//
+// Dates typed by the user, for backfilling or planning entries. Accepts
+// absolute dates (`2024-05-01`, `may 3`, `3 May 2024`) and relative ones
+// (`yesterday`, `last friday`, `2 days ago`, `in 1 week`).
+use std::{error::Error, fmt};
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use regex::Regex;
+
+#[derive(Debug)]
+pub enum DateError {
+    Empty,
+    /// Shaped like a date but not a real one, e.g. `2024-02-30`
+    Invalid(String),
+    Unrecognized(String),
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateError::Empty => write!(f, "No date given"),
+            DateError::Invalid(input) => write!(f, "`{}` is not a valid date", input),
+            DateError::Unrecognized(input) => write!(
+                f,
+                "Can't read `{}` as a date (try 2024-05-01, yesterday, last friday or 3 days ago)",
+                input
+            ),
+        }
+    }
+}
+
+impl Error for DateError {}
+
+/// Parse `input` into a date, resolving relative expressions against `today`.
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate, DateError> {
+    let text = input.trim().to_lowercase().replace(',', " ");
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return Err(DateError::Empty);
+    }
+    let invalid = || DateError::Invalid(input.trim().to_string());
+    let unrecognized = || DateError::Unrecognized(input.trim().to_string());
+
+    // A bare weekday is the latest one up to today, `last`/`next` skip today
+    let (direction, day) = match words.as_slice() {
+        [day] => (None, *day),
+        [direction @ ("last" | "next"), day] => (Some(*direction), *day),
+        _ => (None, ""),
+    };
+    if let Some(day) = weekday(day) {
+        let date = match direction {
+            None => today.checked_sub_days(Days::new(days_between(day, today.weekday()))),
+            Some("last") => match days_between(day, today.weekday()) {
+                0 => today.checked_sub_days(Days::new(7)),
+                back => today.checked_sub_days(Days::new(back)),
+            },
+            _ => match days_between(today.weekday(), day) {
+                0 => today.checked_add_days(Days::new(7)),
+                ahead => today.checked_add_days(Days::new(ahead)),
+            },
+        };
+        return date.ok_or_else(invalid);
+    }
+
+    match words.as_slice() {
+        ["today"] => return Ok(today),
+        ["yesterday"] => return today.checked_sub_days(Days::new(1)).ok_or_else(invalid),
+        ["tomorrow"] => return today.checked_add_days(Days::new(1)).ok_or_else(invalid),
+        ["last", unit] => return shift(today, -1, unit).ok_or_else(unrecognized),
+        ["next", unit] => return shift(today, 1, unit).ok_or_else(unrecognized),
+        [count, unit, "ago"] => {
+            let count = number(count).ok_or_else(unrecognized)?;
+            return shift(today, -count, unit).ok_or_else(unrecognized);
+        }
+        ["in", count, unit] => {
+            let count = number(count).ok_or_else(unrecognized)?;
+            return shift(today, count, unit).ok_or_else(unrecognized);
+        }
+        _ => {}
+    }
+
+    let numeric = Regex::new(r"^(\d{4})[-/.](\d{1,2})[-/.](\d{1,2})$").expect("valid regex");
+    if let Some(caps) = numeric.captures(&text) {
+        let (year, month, day) = (caps[1].parse().ok(), caps[2].parse().ok(), caps[3].parse().ok());
+        return match (year, month, day) {
+            (Some(y), Some(m), Some(d)) => NaiveDate::from_ymd_opt(y, m, d).ok_or_else(invalid),
+            _ => Err(invalid()),
+        };
+    }
+
+    // Month names: `may 3`, `3 may`, `may 3rd 2024`, ...
+    let ordinal = Regex::new(r"\b(\d{1,2})(st|nd|rd|th)\b").expect("valid regex");
+    let text = ordinal.replace_all(&text, "$1");
+    let with_year = if text.split_whitespace().count() == 2 {
+        format!("{} {}", text, today.year())
+    } else {
+        text.to_string()
+    };
+    ["%B %d %Y", "%d %B %Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(&with_year, format).ok())
+        .ok_or_else(unrecognized)
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+// Days from `from` forward to the next `to`, 0 when they're the same day
+fn days_between(from: Weekday, to: Weekday) -> u64 {
+    (to.num_days_from_monday() + 7 - from.num_days_from_monday()) as u64 % 7
+}
+
+// A count of units; never negative, the direction comes from `ago`/`in`
+fn number(word: &str) -> Option<i64> {
+    match word {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        "four" => Some(4),
+        "five" => Some(5),
+        _ => word.parse().ok().filter(|n: &i64| *n >= 0),
+    }
+}
+
+// `today` moved by `count` days, weeks, months or years
+fn shift(today: NaiveDate, count: i64, unit: &str) -> Option<NaiveDate> {
+    let unit = unit.strip_suffix('s').unwrap_or(unit);
+    let (days, months) = match unit {
+        "day" => (count, 0),
+        "week" => (count.checked_mul(7)?, 0),
+        "month" => (0, count),
+        "year" => (0, count.checked_mul(12)?),
+        _ => return None,
+    };
+    let days = Days::new(days.unsigned_abs());
+    let months = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if count < 0 {
+        today.checked_sub_days(days)?.checked_sub_months(months)
+    } else {
+        today.checked_add_days(days)?.checked_add_months(months)
+    }
+}
@@ -0,0 +1,96 @@
+// src/journal/editor.rs
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::Path,
+    process::{Command, ExitStatus},
+};
+
+/// Why an editor session didn't complete.
+#[derive(Debug)]
+pub enum EditorError {
+    /// The editor setting couldn't be split into words
+    Invalid(String),
+    NotFound(String),
+    Spawn(String, io::Error),
+    Failed(String, ExitStatus),
+}
+
+impl fmt::Display for EditorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditorError::Invalid(message) => write!(f, "Invalid editor command: {}", message),
+            EditorError::NotFound(program) => write!(
+                f,
+                "Editor `{}` not found (set one with `notes config editor <cmd>`)",
+                program
+            ),
+            EditorError::Spawn(program, err) => write!(f, "Failed to start `{}`: {}", program, err),
+            EditorError::Failed(program, status) => match status.code() {
+                Some(code) => write!(f, "`{}` exited with status {}", program, code),
+                None => write!(f, "`{}` was terminated", program),
+            },
+        }
+    }
+}
+
+impl Error for EditorError {}
+
+// Editors known to take `+N` to open at line N
+const PLUS_LINE_EDITORS: [&str; 8] = ["vi", "vim", "nvim", "nano", "emacs", "micro", "kak", "joe"];
+
+/// Build the command for `editor` (as written in the config or `$EDITOR`).
+///
+/// Arguments are split like a shell would. `{file}` and `{line}` in any
+/// argument are replaced with the entry path and its last line; without a
+/// `{file}` placeholder the path is appended. For common terminal editors
+/// the cursor is placed on the last line automatically.
+pub fn editor_command(editor: &str, path: &Path) -> Result<Command, EditorError> {
+    let words = shell_words::split(editor).map_err(|err| EditorError::Invalid(err.to_string()))?;
+    let Some((program, args)) = words.split_first() else {
+        return Err(EditorError::Invalid("empty command".to_string()));
+    };
+
+    let file = path.to_string_lossy();
+    let line = last_line(path).to_string();
+    let has_file = args.iter().any(|arg| arg.contains("{file}"));
+    let has_line = args.iter().any(|arg| arg.contains("{line}"));
+
+    let mut command = Command::new(program);
+    let program_name = Path::new(program)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(program);
+    if !has_file && !has_line && PLUS_LINE_EDITORS.contains(&program_name) {
+        command.arg(format!("+{}", line));
+    }
+    command.args(
+        args.iter()
+            .map(|arg| arg.replace("{file}", &file).replace("{line}", &line)),
+    );
+    if !has_file {
+        command.arg(path);
+    }
+    Ok(command)
+}
+
+/// Run `editor` on `path` and wait for it to exit.
+pub fn run_editor(editor: &str, path: &Path) -> Result<(), EditorError> {
+    let mut command = editor_command(editor, path)?;
+    let program = command.get_program().to_string_lossy().to_string();
+    let status = command.status().map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => EditorError::NotFound(program.clone()),
+        _ => EditorError::Spawn(program.clone(), err),
+    })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(EditorError::Failed(program, status))
+    }
+}
+
+fn last_line(path: &Path) -> usize {
+    fs::read_to_string(path)
+        .map(|content| content.lines().count().max(1))
+        .unwrap_or(1)
+}
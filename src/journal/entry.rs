@@ -1,10 +1,104 @@
 // src/journal/entry.rs
+use std::path::PathBuf;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use ratatui::widgets::ListState;
 
+use crate::journal::{
+    frontmatter::{self, FrontMatter},
+    tags::same_tag,
+};
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+pub const TIME_SUFFIX_FORMAT: &str = "%H%M%S";
+
+#[derive(Clone)]
 pub struct JournalEntry {
+    /// File stem, e.g. `2024-05-01_093012` (or `2024-05-01` for legacy one-per-day notes)
+    pub id: String,
     pub date: String,
+    pub timestamp: NaiveDateTime,
+    pub path: PathBuf,
     pub size: u64,
-    pub content: String,
+    pub meta: FrontMatter,
+    /// First markdown heading of the body
+    pub heading: Option<String>,
+    /// `#tags` written in the body
+    pub inline_tags: Vec<String>,
+    /// Full file text; `None` until loaded (entries listed from the index carry metadata only)
+    pub content: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn content(&self) -> &str {
+        self.content.as_deref().unwrap_or_default()
+    }
+
+    /// Markdown after the front matter block.
+    pub fn body(&self) -> &str {
+        frontmatter::split(self.content()).1
+    }
+
+    /// Front matter title, falling back to the first heading in the body.
+    pub fn title(&self) -> Option<String> {
+        self.meta.title.clone().or_else(|| self.heading.clone())
+    }
+
+    /// Front matter tags followed by inline ones, without duplicates.
+    pub fn tags(&self) -> Vec<&str> {
+        let mut all: Vec<&str> = Vec::new();
+        for tag in self.meta.tags.iter().chain(&self.inline_tags) {
+            if !all.iter().any(|t| same_tag(t, tag)) {
+                all.push(tag);
+            }
+        }
+        all
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.meta.tags.iter().chain(&self.inline_tags).any(|t| same_tag(t, tag))
+    }
+
+    /// Time of day shown next to the date; legacy daily notes have none.
+    pub fn time_label(&self) -> Option<String> {
+        if self.id.len() > self.date.len() {
+            Some(self.timestamp.format("%H:%M").to_string())
+        } else {
+            None
+        }
+    }
+
+    /// The date, followed by the time when the entry has one.
+    pub fn date_label(&self) -> String {
+        match self.time_label() {
+            Some(time) => format!("{} {}", self.date, time),
+            None => self.date.clone(),
+        }
+    }
+}
+
+/// Build the id for a note created at `timestamp`.
+pub fn entry_id(timestamp: NaiveDateTime) -> String {
+    format!(
+        "{}_{}",
+        timestamp.format(DATE_FORMAT),
+        timestamp.format(TIME_SUFFIX_FORMAT)
+    )
+}
+
+/// Parse an entry id back into its timestamp. Accepts both `YYYY-MM-DD_HHMMSS`
+/// and the legacy `YYYY-MM-DD` naming.
+pub fn parse_entry_id(id: &str) -> Option<NaiveDateTime> {
+    let (date_part, time_part) = match id.split_once('_') {
+        Some((date, time)) => (date, Some(time)),
+        None => (id, None),
+    };
+    let date = NaiveDate::parse_from_str(date_part, DATE_FORMAT).ok()?;
+    let time = match time_part {
+        Some(time) => NaiveTime::parse_from_str(time, TIME_SUFFIX_FORMAT).ok()?,
+        None => NaiveTime::MIN,
+    };
+    Some(date.and_time(time))
 }
 
 pub struct StatefulList<T> {
@@ -21,7 +115,14 @@ impl<T> StatefulList<T> {
         StatefulList { state, items }
     }
 
+    pub fn selected_item(&self) -> Option<&T> {
+        self.state.selected().and_then(|i| self.items.get(i))
+    }
+
     pub fn next(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
                 if i >= self.items.len() - 1 {
@@ -36,6 +137,9 @@ impl<T> StatefulList<T> {
     }
 
     pub fn previous(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
                 if i == 0 {
@@ -0,0 +1,237 @@
+// src/journal/export.rs
+//
+// Picking the entries an export covers, and the single-file export formats.
+// The JSON layout is documented in the README; bump `JSON_VERSION` on any
+// change that could break a consumer.
+use std::{collections::BTreeMap, error::Error};
+
+use chrono::{Local, NaiveDate};
+use clap::ValueEnum;
+use pulldown_cmark::{Event, Tag, TagEnd};
+use serde::Serialize;
+
+use crate::{
+    config::settings::active_journal,
+    journal::{
+        entry::JournalEntry,
+        storage::{load_content, load_entries},
+    },
+    ui::markdown,
+};
+
+pub const JSON_VERSION: u32 = 1;
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Entries one after another under a heading each
+    Markdown,
+    /// Entries with all their metadata, see the README for the schema
+    Json,
+    /// Plain text with the Markdown formatting removed
+    Txt,
+}
+
+/// Which entries to export. Empty fields don't restrict anything.
+#[derive(Default)]
+pub struct ExportFilter {
+    /// First day to include
+    pub from: Option<NaiveDate>,
+    /// Last day to include
+    pub to: Option<NaiveDate>,
+    /// Entries tagged with any of these, in the front matter or inline
+    /// (case-insensitive)
+    pub tags: Vec<String>,
+}
+
+impl ExportFilter {
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        let day = entry.timestamp.date();
+        self.from.is_none_or(|from| day >= from)
+            && self.to.is_none_or(|to| day <= to)
+            && (self.tags.is_empty() || self.tags.iter().any(|tag| entry.has_tag(tag)))
+    }
+}
+
+/// Entries of the active journal passing `filter`, oldest first, with their
+/// content loaded.
+pub fn select_entries(filter: &ExportFilter) -> Result<Vec<JournalEntry>, Box<dyn Error>> {
+    let mut entries: Vec<JournalEntry> = load_entries()?
+        .into_iter()
+        .filter(|entry| filter.matches(entry))
+        .collect();
+    entries.reverse();
+    entries.iter_mut().for_each(load_content);
+    Ok(entries)
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    version: u32,
+    journal: String,
+    exported_at: String,
+    entries: Vec<JsonEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonEntry<'a> {
+    id: &'a str,
+    date: &'a str,
+    timestamp: String,
+    path: String,
+    size: u64,
+    title: Option<String>,
+    heading: Option<&'a str>,
+    tags: &'a [String],
+    inline_tags: &'a [String],
+    category: Option<&'a str>,
+    mood: Option<&'a str>,
+    custom: BTreeMap<&'a str, String>,
+    content: &'a str,
+    body: &'a str,
+}
+
+/// Render `entries` (oldest first) as one document in `format`.
+pub fn export_bundle(entries: &[JournalEntry], format: ExportFormat) -> Result<String, Box<dyn Error>> {
+    Ok(match format {
+        ExportFormat::Markdown => entries.iter().map(markdown_section).collect::<Vec<_>>().join("\n"),
+        ExportFormat::Txt => entries.iter().map(text_section).collect::<Vec<_>>().join("\n"),
+        ExportFormat::Json => {
+            let export = JsonExport {
+                version: JSON_VERSION,
+                journal: active_journal(),
+                exported_at: Local::now().naive_local().format(TIMESTAMP_FORMAT).to_string(),
+                entries: entries.iter().map(json_entry).collect(),
+            };
+            serde_json::to_string_pretty(&export)? + "\n"
+        }
+    })
+}
+
+fn json_entry(entry: &JournalEntry) -> JsonEntry<'_> {
+    let meta = &entry.meta;
+    JsonEntry {
+        id: &entry.id,
+        date: &entry.date,
+        timestamp: entry.timestamp.format(TIMESTAMP_FORMAT).to_string(),
+        path: entry.path.display().to_string(),
+        size: entry.size,
+        title: entry.title(),
+        heading: entry.heading.as_deref(),
+        tags: &meta.tags,
+        inline_tags: &entry.inline_tags,
+        category: meta.category.as_deref(),
+        mood: meta.mood.as_deref(),
+        custom: meta
+            .custom
+            .iter()
+            .filter_map(|(key, _)| Some((key.as_str(), meta.get(key)?)))
+            .collect(),
+        content: entry.content(),
+        body: entry.body(),
+    }
+}
+
+// Metadata worth repeating above the body, one `Key: value` per item
+fn meta_lines(entry: &JournalEntry) -> Vec<String> {
+    let meta = &entry.meta;
+    let mut lines = Vec::new();
+    let tags = entry.tags();
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags.iter().map(|t| format!("#{t}")).collect();
+        lines.push(format!("Tags: {}", tags.join(" ")));
+    }
+    if let Some(category) = &meta.category {
+        lines.push(format!("Category: {category}"));
+    }
+    if let Some(mood) = &meta.mood {
+        lines.push(format!("Mood: {mood}"));
+    }
+    lines
+}
+
+fn markdown_section(entry: &JournalEntry) -> String {
+    let mut section = match &entry.meta.title {
+        Some(title) => format!("# {} — {}\n\n", entry.date_label(), title),
+        None => format!("# {}\n\n", entry.date_label()),
+    };
+    let meta = meta_lines(entry);
+    for line in &meta {
+        section.push_str(&format!("*{line}*  \n"));
+    }
+    if !meta.is_empty() {
+        section.push('\n');
+    }
+    // Push the entry's own headings down a level so they nest under ours
+    let mut in_code = false;
+    for line in entry.body().trim().lines() {
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_code = !in_code;
+        }
+        if !in_code && line.starts_with('#') && line.trim_start_matches('#').starts_with(' ') {
+            section.push('#');
+        }
+        section.push_str(line);
+        section.push('\n');
+    }
+    section
+}
+
+fn text_section(entry: &JournalEntry) -> String {
+    let heading = match &entry.meta.title {
+        Some(title) => format!("{} - {}", entry.date_label(), title),
+        None => entry.date_label(),
+    };
+    let mut section = format!("{}\n{}\n", heading, "=".repeat(heading.chars().count()));
+    for line in meta_lines(entry) {
+        section.push_str(&line);
+        section.push('\n');
+    }
+    section.push('\n');
+    section.push_str(plain_text(entry.body()).trim());
+    section.push('\n');
+    section
+}
+
+// Strip Markdown syntax from `body`, keeping its text and paragraph breaks
+fn plain_text(body: &str) -> String {
+    let mut text = String::new();
+    let mut depth = 0usize;
+    let end_line = |text: &mut String| {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+    };
+    for event in markdown::parser(body) {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak => text.push('\n'),
+            Event::TaskListMarker(done) => text.push_str(if done { "[x] " } else { "[ ] " }),
+            Event::Rule => text.push_str("----\n\n"),
+            Event::Start(Tag::List(_)) => {
+                end_line(&mut text);
+                depth += 1;
+            }
+            Event::Start(Tag::Item) => {
+                end_line(&mut text);
+                text.push_str(&"  ".repeat(depth.saturating_sub(1)));
+                text.push_str("- ");
+            }
+            Event::End(TagEnd::Item) => end_line(&mut text),
+            Event::End(TagEnd::List(_)) => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    text.push('\n');
+                }
+            }
+            Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock) => {
+                end_line(&mut text);
+                if depth == 0 {
+                    text.push('\n');
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
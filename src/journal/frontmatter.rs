@@ -0,0 +1,310 @@
+// src/journal/frontmatter.rs
+//
+// Metadata block at the top of an entry, either YAML style:
+//
+//     ---
+//     title: Standup
+//     tags: [work, planning]
+//     ---
+//
+// or TOML style, fenced with `+++` and using `key = "value"`.
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum FrontMatterStyle {
+    #[default]
+    Yaml,
+    Toml,
+}
+
+impl FrontMatterStyle {
+    fn fence(self) -> &'static str {
+        match self {
+            FrontMatterStyle::Yaml => "---",
+            FrontMatterStyle::Toml => "+++",
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct FrontMatter {
+    pub style: FrontMatterStyle,
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    pub category: Option<String>,
+    pub mood: Option<String>,
+    /// Keys we don't interpret, with their raw value text kept verbatim
+    pub custom: Vec<(String, String)>,
+    /// Key order as found in the file so rewrites don't shuffle lines
+    order: Vec<String>,
+}
+
+const KNOWN_KEYS: [&str; 4] = ["title", "tags", "category", "mood"];
+
+impl FrontMatter {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.tags.is_empty()
+            && self.category.is_none()
+            && self.mood.is_none()
+            && self.custom.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        match key {
+            "title" => self.title.clone(),
+            "tags" => (!self.tags.is_empty()).then(|| self.tags.join(", ")),
+            "category" => self.category.clone(),
+            "mood" => self.mood.clone(),
+            _ => self
+                .custom
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| unquote(v.trim())),
+        }
+    }
+
+    /// Set a field from user input; an empty value clears it.
+    pub fn set(&mut self, key: &str, value: &str) {
+        let value = value.trim();
+        let optional = |v: &str| (!v.is_empty()).then(|| v.to_string());
+        match key {
+            "title" => self.title = optional(value),
+            "tags" => self.tags = parse_list(value),
+            "category" => self.category = optional(value),
+            "mood" => self.mood = optional(value),
+            _ => {
+                self.custom.retain(|(k, _)| k != key);
+                if !value.is_empty() {
+                    let raw = match self.style {
+                        FrontMatterStyle::Yaml => yaml_scalar(value),
+                        FrontMatterStyle::Toml => toml_string(value),
+                    };
+                    self.custom.push((key.to_string(), raw));
+                }
+            }
+        }
+        if !self.order.iter().any(|k| k == key) {
+            self.order.push(key.to_string());
+        }
+    }
+
+    /// Render the block including its fences and a trailing newline.
+    pub fn render(&self) -> String {
+        let mut keys: Vec<&str> = self.order.iter().map(String::as_str).collect();
+        for key in KNOWN_KEYS {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        for (key, _) in &self.custom {
+            if !keys.contains(&key.as_str()) {
+                keys.push(key);
+            }
+        }
+
+        let fence = self.style.fence();
+        let mut out = format!("{fence}\n");
+        for key in keys {
+            if let Some(line) = self.render_key(key) {
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        out.push_str(fence);
+        out.push('\n');
+        out
+    }
+
+    fn render_key(&self, key: &str) -> Option<String> {
+        let (yaml, toml) = match key {
+            "title" => {
+                let v = self.title.as_deref()?;
+                (yaml_scalar(v), toml_string(v))
+            }
+            "category" => {
+                let v = self.category.as_deref()?;
+                (yaml_scalar(v), toml_string(v))
+            }
+            "mood" => {
+                let v = self.mood.as_deref()?;
+                (yaml_scalar(v), toml_string(v))
+            }
+            "tags" => {
+                if self.tags.is_empty() {
+                    return None;
+                }
+                let yaml = format!(
+                    "[{}]",
+                    self.tags.iter().map(|t| yaml_scalar(t)).collect::<Vec<_>>().join(", ")
+                );
+                let toml = format!(
+                    "[{}]",
+                    self.tags.iter().map(|t| toml_string(t)).collect::<Vec<_>>().join(", ")
+                );
+                (yaml, toml)
+            }
+            _ => {
+                let (_, raw) = self.custom.iter().find(|(k, _)| k == key)?;
+                return Some(match self.style {
+                    FrontMatterStyle::Yaml if raw.starts_with('\n') => format!("{key}:{raw}"),
+                    FrontMatterStyle::Yaml => format!("{key}: {raw}"),
+                    FrontMatterStyle::Toml => format!("{key} = {raw}"),
+                });
+            }
+        };
+        Some(match self.style {
+            FrontMatterStyle::Yaml => format!("{key}: {yaml}"),
+            FrontMatterStyle::Toml => format!("{key} = {toml}"),
+        })
+    }
+}
+
+/// Split a file into its front matter (if any) and the markdown body.
+pub fn split(content: &str) -> (Option<FrontMatter>, &str) {
+    let style = if content.starts_with("---") {
+        FrontMatterStyle::Yaml
+    } else if content.starts_with("+++") {
+        FrontMatterStyle::Toml
+    } else {
+        return (None, content);
+    };
+    let fence = style.fence();
+
+    let mut lines = content.split_inclusive('\n');
+    let mut offset = match lines.next() {
+        Some(first) if first.trim_end() == fence => first.len(),
+        _ => return (None, content),
+    };
+
+    let mut block = Vec::new();
+    for line in lines {
+        offset += line.len();
+        if line.trim_end() == fence {
+            let rest = &content[offset..];
+            let body = rest
+                .strip_prefix("\r\n")
+                .or_else(|| rest.strip_prefix('\n'))
+                .unwrap_or(rest);
+            return (Some(parse_block(&block, style)), body);
+        }
+        block.push(line.trim_end_matches(['\r', '\n']));
+    }
+
+    // No closing fence: treat the whole file as body
+    (None, content)
+}
+
+/// Replace (or add) the front matter of `content`, leaving the body untouched.
+pub fn with_front_matter(content: &str, meta: &FrontMatter) -> String {
+    let (_, body) = split(content);
+    if meta.is_empty() {
+        return body.to_string();
+    }
+    format!("{}\n{}", meta.render(), body)
+}
+
+fn parse_block(lines: &[&str], style: FrontMatterStyle) -> FrontMatter {
+    let separator = match style {
+        FrontMatterStyle::Yaml => ':',
+        FrontMatterStyle::Toml => '=',
+    };
+
+    // Group continuation lines (indented YAML lists etc.) with their key
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for line in lines {
+        let is_continuation = line.starts_with([' ', '\t']) || line.trim_start().starts_with("- ");
+        match line.split_once(separator) {
+            Some((key, value)) if !is_continuation && !key.trim().is_empty() => {
+                let value = value.strip_prefix(' ').unwrap_or(value);
+                fields.push((key.trim().to_string(), value.to_string()));
+            }
+            _ => {
+                if let Some((_, value)) = fields.last_mut() {
+                    value.push('\n');
+                    value.push_str(line);
+                }
+            }
+        }
+    }
+
+    let mut meta = FrontMatter { style, ..Default::default() };
+    for (key, raw) in fields {
+        let value = unquote(raw.trim());
+        let optional = (!value.is_empty()).then(|| value.clone());
+        match key.as_str() {
+            "title" => meta.title = optional,
+            "category" => meta.category = optional,
+            "mood" => meta.mood = optional,
+            "tags" => meta.tags = parse_list(raw.trim()),
+            _ => meta.custom.push((key.clone(), raw)),
+        }
+        meta.order.push(key);
+    }
+    meta
+}
+
+/// Accepts `[a, "b"]`, a YAML block list (`- a` lines) or a plain comma list.
+fn parse_list(raw: &str) -> Vec<String> {
+    let raw = raw.trim();
+    let items: Vec<&str> = if raw.starts_with('[') && raw.ends_with(']') {
+        raw[1..raw.len() - 1].split(',').collect()
+    } else if raw.lines().any(|l| l.trim_start().starts_with("- ")) {
+        raw.lines()
+            .filter_map(|l| l.trim_start().strip_prefix("- "))
+            .collect()
+    } else {
+        raw.split(',').collect()
+    };
+    items
+        .into_iter()
+        .map(|item| unquote(item.trim()).trim_start_matches('#').to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+// YAML quoting: backslash escapes only apply inside double quotes; single
+// quotes only escape `''`
+fn unquote(value: &str) -> String {
+    let quoted = |q: char| value.len() >= 2 && value.starts_with(q) && value.ends_with(q);
+    if quoted('\'') {
+        return value[1..value.len() - 1].replace("''", "'");
+    }
+    if !quoted('"') {
+        return value.to_string();
+    }
+    let inner = &value[1..value.len() - 1];
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(c @ ('"' | '\\')) => out.push(c),
+            Some(c) => {
+                out.push('\\');
+                out.push(c);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn yaml_scalar(value: &str) -> String {
+    let needs_quotes = value.contains([':', '#', ',', '[', ']', '{', '}', '"'])
+        || value.starts_with(['-', '\'', '&', '*', '!', '|', '>', '%', '@', '`', ' '])
+        || value.ends_with(' ');
+    if needs_quotes {
+        toml_string(value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn toml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,324 @@
+// src/journal/git.rs
+//
+// Optional git versioning of the journal directory (`git.enabled`). Changes
+// made through the app are committed as they happen and `sync` rebases onto
+// and pushes to `origin`. Everything goes through the `git` command so the
+// user's own configuration (credentials, signing, hooks) applies.
+use std::{
+    error::Error,
+    fmt, fs,
+    path::Path,
+    process::{Command, Output},
+};
+
+use crate::config::settings::{config, get_journal_dir};
+
+const REMOTE: &str = "origin";
+// Local bookkeeping that shouldn't travel between machines
+const GITIGNORE: &str = ".history/\n.trash/\n";
+
+#[derive(Debug)]
+pub enum GitError {
+    Disabled,
+    NoRemote,
+    /// `git` exited with an error; holds the arguments and its stderr
+    Failed(String, String),
+    Spawn(std::io::Error),
+    Io(std::io::Error),
+    /// Pulling hit conflicts in these files; the rebase was aborted
+    Conflict(Vec<String>),
+}
+
+impl fmt::Display for GitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GitError::Disabled => write!(f, "git sync is off (enable it with `notes config git.enabled true`)"),
+            GitError::NoRemote => write!(f, "no remote configured (set one with `notes config git.remote <url>`)"),
+            GitError::Failed(args, stderr) => write!(f, "`git {}` failed: {}", args, stderr),
+            GitError::Spawn(err) => write!(f, "couldn't run git: {}", err),
+            GitError::Io(err) => write!(f, "git setup failed: {}", err),
+            GitError::Conflict(files) => write!(
+                f,
+                "sync conflict in {}; resolve it in the journal repository, local changes are kept",
+                files.join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for GitError {}
+
+fn git(dir: &Path, args: &[&str]) -> Result<Output, GitError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(GitError::Spawn)?;
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(GitError::Failed(
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+fn stdout(output: Output) -> String {
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Make `dir` a repository with an ignore file and `origin` pointing at
+/// `remote`, if there is one. An `origin` with another URL is repointed.
+fn ensure_repo(dir: &Path, remote: Option<&str>) -> Result<(), GitError> {
+    if !dir.join(".git").exists() {
+        git(dir, &["init", "--quiet"])?;
+    }
+    let ignore = dir.join(".gitignore");
+    if !ignore.exists() {
+        fs::write(&ignore, GITIGNORE).map_err(GitError::Io)?;
+    }
+    if let Some(url) = remote {
+        match git(dir, &["remote", "get-url", REMOTE]).map(stdout) {
+            Ok(current) if current == url => {}
+            Ok(_) => {
+                git(dir, &["remote", "set-url", REMOTE, url])?;
+            }
+            Err(_) => {
+                git(dir, &["remote", "add", REMOTE, url])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Fallback identity so commits and rebases work on machines where git was
+// never configured
+fn identity(dir: &Path) -> Vec<&'static str> {
+    if git(dir, &["config", "user.email"]).is_ok() {
+        Vec::new()
+    } else {
+        vec!["-c", "user.name=termjournal", "-c", "user.email=termjournal@localhost"]
+    }
+}
+
+// Commit everything in `dir`. Returns false when there was nothing to commit.
+fn commit(dir: &Path, message: &str) -> Result<bool, GitError> {
+    git(dir, &["add", "--all"])?;
+    if git(dir, &["diff", "--cached", "--quiet"]).is_ok() {
+        return Ok(false);
+    }
+    let mut args = identity(dir);
+    args.extend(["commit", "--quiet", "-m", message]);
+    git(dir, &args)?;
+    Ok(true)
+}
+
+/// Commit all changes in the journal with `message`. Does nothing unless
+/// `git.enabled` is set.
+pub fn auto_commit(message: &str) -> Result<(), GitError> {
+    if !config().git.enabled {
+        return Ok(());
+    }
+    let dir = get_journal_dir();
+    ensure_repo(&dir, config().git.remote.as_deref())?;
+    commit(&dir, message)?;
+    Ok(())
+}
+
+/// Commit local changes, rebase them onto `origin` and push. Returns a
+/// summary for the user.
+pub fn sync() -> Result<String, GitError> {
+    if !config().git.enabled {
+        return Err(GitError::Disabled);
+    }
+    let dir = get_journal_dir();
+    ensure_repo(&dir, config().git.remote.as_deref())?;
+    sync_repo(&dir)
+}
+
+// `sync` for the repository in `dir`
+fn sync_repo(dir: &Path) -> Result<String, GitError> {
+    if git(dir, &["remote", "get-url", REMOTE]).is_err() {
+        return Err(GitError::NoRemote);
+    }
+    commit(dir, "Sync journal")?;
+
+    let has_commits = git(dir, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok();
+    let branch = if has_commits {
+        stdout(git(dir, &["rev-parse", "--abbrev-ref", "HEAD"])?)
+    } else {
+        stdout(git(dir, &["symbolic-ref", "--short", "HEAD"])?)
+    };
+
+    // A fresh remote has nothing to pull yet
+    let remote_has_branch = !stdout(git(dir, &["ls-remote", "--heads", REMOTE, &branch])?).is_empty();
+    let mut pulled = false;
+    if remote_has_branch {
+        let mut pull = identity(dir);
+        pull.push("pull");
+        if has_commits {
+            pull.push("--rebase");
+        }
+        pull.extend(["--quiet", REMOTE, &branch]);
+        if let Err(err) = git(dir, &pull) {
+            let conflicts = stdout(git(dir, &["diff", "--name-only", "--diff-filter=U"])?);
+            if conflicts.is_empty() {
+                return Err(err);
+            }
+            let _ = git(dir, &["rebase", "--abort"]);
+            return Err(GitError::Conflict(conflicts.lines().map(str::to_string).collect()));
+        }
+        pulled = true;
+    }
+
+    if git(dir, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_err() {
+        return Ok("Nothing to sync yet".to_string());
+    }
+    git(dir, &["push", "--quiet", "--set-upstream", REMOTE, &branch])?;
+    Ok(if pulled {
+        format!("Synced {} with {}", branch, REMOTE)
+    } else {
+        format!("Pushed {} to {}", branch, REMOTE)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use super::*;
+
+    // A scratch directory with a bare `remote.git` and a `journal` repository
+    // tracking it, removed again when dropped
+    struct Scratch {
+        root: PathBuf,
+    }
+
+    impl Scratch {
+        fn new(name: &str) -> Scratch {
+            let root = env::temp_dir().join(format!("termjournal-git-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(root.join("journal")).unwrap();
+            git(&root, &["init", "--quiet", "--bare", "remote.git"]).unwrap();
+            let scratch = Scratch { root };
+            ensure_repo(&scratch.journal(), Some(scratch.remote().to_str().unwrap())).unwrap();
+            scratch
+        }
+
+        fn journal(&self) -> PathBuf {
+            self.root.join("journal")
+        }
+
+        fn remote(&self) -> PathBuf {
+            self.root.join("remote.git")
+        }
+
+        // A second machine: a clone of the remote that commits and pushes `file`
+        fn push_from_clone(&self, file: &str, content: &str) {
+            let clone = self.root.join("clone");
+            if !clone.exists() {
+                git(&self.root, &["clone", "--quiet", self.remote().to_str().unwrap(), "clone"]).unwrap();
+            }
+            fs::write(clone.join(file), content).unwrap();
+            assert!(commit(&clone, &format!("Edit {file} elsewhere")).unwrap());
+            git(&clone, &["push", "--quiet", REMOTE, "HEAD"]).unwrap();
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    fn log(dir: &Path, rev: &str) -> Vec<String> {
+        let output = git(dir, &["log", "--format=%s", rev]).unwrap();
+        stdout(output).lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn commit_records_changes() {
+        let scratch = Scratch::new("commit");
+        let journal = scratch.journal();
+        fs::write(journal.join("2024-05-01_090000.md"), "# Hello\n").unwrap();
+
+        assert!(commit(&journal, "Add 2024-05-01_090000").unwrap());
+        assert_eq!(log(&journal, "HEAD"), ["Add 2024-05-01_090000"]);
+        let files = stdout(git(&journal, &["ls-files"]).unwrap());
+        assert_eq!(files, ".gitignore\n2024-05-01_090000.md");
+        // Nothing left to commit
+        assert!(!commit(&journal, "Again").unwrap());
+    }
+
+    #[test]
+    fn changed_remote_is_repointed() {
+        let scratch = Scratch::new("remote");
+        let journal = scratch.journal();
+        let moved = scratch.root.join("moved.git");
+        git(&scratch.root, &["init", "--quiet", "--bare", "moved.git"]).unwrap();
+
+        ensure_repo(&journal, Some(moved.to_str().unwrap())).unwrap();
+        let url = stdout(git(&journal, &["remote", "get-url", REMOTE]).unwrap());
+        assert_eq!(url, moved.to_str().unwrap());
+        fs::write(journal.join("a.md"), "a\n").unwrap();
+        sync_repo(&journal).unwrap();
+        assert!(git(&moved, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok());
+    }
+
+    #[test]
+    fn sync_pushes_to_empty_remote() {
+        let scratch = Scratch::new("push");
+        let journal = scratch.journal();
+        fs::write(journal.join("2024-05-01_090000.md"), "# Hello\n").unwrap();
+
+        let summary = sync_repo(&journal).unwrap();
+        assert!(summary.starts_with("Pushed"), "{summary}");
+        let local = stdout(git(&journal, &["rev-parse", "HEAD"]).unwrap());
+        let branch = stdout(git(&journal, &["rev-parse", "--abbrev-ref", "HEAD"]).unwrap());
+        let remote = stdout(git(&scratch.remote(), &["rev-parse", &branch]).unwrap());
+        assert_eq!(local, remote);
+    }
+
+    #[test]
+    fn sync_rebases_onto_diverged_remote() {
+        let scratch = Scratch::new("rebase");
+        let journal = scratch.journal();
+        fs::write(journal.join("a.md"), "a\n").unwrap();
+        sync_repo(&journal).unwrap();
+
+        scratch.push_from_clone("b.md", "b\n");
+        fs::write(journal.join("c.md"), "c\n").unwrap();
+        let summary = sync_repo(&journal).unwrap();
+
+        assert!(summary.starts_with("Synced"), "{summary}");
+        assert!(journal.join("b.md").exists());
+        // Local work sits on top of the remote's, without a merge commit
+        assert_eq!(log(&journal, "HEAD"), ["Sync journal", "Edit b.md elsewhere", "Sync journal"]);
+        assert!(stdout(git(&journal, &["rev-list", "--merges", "HEAD"]).unwrap()).is_empty());
+        let branch = stdout(git(&journal, &["rev-parse", "--abbrev-ref", "HEAD"]).unwrap());
+        assert_eq!(log(&scratch.remote(), &branch), log(&journal, "HEAD"));
+    }
+
+    #[test]
+    fn sync_conflict_aborts_rebase() {
+        let scratch = Scratch::new("conflict");
+        let journal = scratch.journal();
+        fs::write(journal.join("entry.md"), "one\n").unwrap();
+        sync_repo(&journal).unwrap();
+
+        scratch.push_from_clone("entry.md", "two\n");
+        fs::write(journal.join("entry.md"), "three\n").unwrap();
+
+        match sync_repo(&journal) {
+            Err(GitError::Conflict(files)) => assert_eq!(files, ["entry.md"]),
+            other => panic!("expected a conflict, got {other:?}"),
+        }
+        let git_dir = journal.join(".git");
+        assert!(!git_dir.join("rebase-merge").exists() && !git_dir.join("rebase-apply").exists());
+        assert_eq!(fs::read_to_string(journal.join("entry.md")).unwrap(), "three\n");
+        assert_eq!(log(&journal, "HEAD"), ["Sync journal", "Sync journal"]);
+        assert!(stdout(git(&journal, &["status", "--porcelain"]).unwrap()).is_empty());
+    }
+}
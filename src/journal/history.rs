@@ -0,0 +1,170 @@
+// src/journal/history.rs
+//
+// Every version of an entry we come across is kept gzip-compressed under
+// `<journal>/.history/<id>/<timestamp>.md.gz`. Versions are recorded right
+// before and after the app writes an entry, and when the index sees that an
+// entry it already knew changed on disk, so the newest revision normally
+// matches the file and the one before it is what the last edit replaced.
+use std::{
+    error::Error,
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{Duration, Local, NaiveDateTime};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use similar::{ChangeTag, TextDiff};
+
+use crate::{
+    config::settings::get_journal_dir,
+    journal::{
+        crypto,
+        storage::{read_entry, write_entry},
+    },
+};
+
+const REVISION_FORMAT: &str = "%Y%m%d-%H%M%S%.3f";
+const REVISION_SUFFIX: &str = ".md.gz";
+const DIFF_CONTEXT: usize = 3;
+
+pub struct Revision {
+    pub saved_at: NaiveDateTime,
+    pub path: PathBuf,
+}
+
+impl Revision {
+    pub fn content(&self) -> Result<String, Box<dyn Error>> {
+        let mut data = fs::read(&self.path)?;
+        if crypto::is_encrypted() {
+            data = crypto::decrypt_bytes(&data)?;
+        }
+        let mut content = String::new();
+        GzDecoder::new(data.as_slice()).read_to_string(&mut content)?;
+        Ok(content)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Context,
+    Added,
+    Removed,
+    /// Gap between two hunks
+    Separator,
+}
+
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+pub fn history_dir() -> PathBuf {
+    get_journal_dir().join(".history")
+}
+
+/// Revisions of entry `id`, newest first.
+pub fn revisions(id: &str) -> Result<Vec<Revision>, Box<dyn Error>> {
+    let mut revisions = Vec::new();
+    let Ok(files) = fs::read_dir(history_dir().join(id)) else {
+        return Ok(revisions);
+    };
+    for file in files.flatten() {
+        let path = file.path();
+        let Some(stamp) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(REVISION_SUFFIX))
+        else {
+            continue;
+        };
+        if let Ok(saved_at) = NaiveDateTime::parse_from_str(stamp, REVISION_FORMAT) {
+            revisions.push(Revision { saved_at, path });
+        }
+    }
+    revisions.sort_by_key(|r| std::cmp::Reverse(r.saved_at));
+    Ok(revisions)
+}
+
+/// Store `content` as a new revision of `id` unless it matches the newest one.
+/// Returns whether a revision was written.
+pub fn record(id: &str, content: &str) -> Result<bool, Box<dyn Error>> {
+    let existing = revisions(id)?;
+    if let Some(latest) = existing.first()
+        && latest.content().is_ok_and(|latest| latest == content)
+    {
+        return Ok(false);
+    }
+
+    let dir = history_dir().join(id);
+    fs::create_dir_all(&dir)?;
+    let mut saved_at = Local::now().naive_local();
+    // Keep names unique and ordered even for saves within the same millisecond
+    if let Some(latest) = existing.first()
+        && saved_at <= latest.saved_at
+    {
+        saved_at = latest.saved_at + Duration::milliseconds(1);
+    }
+    let path = dir.join(format!("{}{}", saved_at.format(REVISION_FORMAT), REVISION_SUFFIX));
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content.as_bytes())?;
+    let mut data = encoder.finish()?;
+    // Compress first; ciphertext doesn't compress
+    if crypto::is_encrypted() {
+        data = crypto::encrypt_bytes(&data)?;
+    }
+    fs::write(&path, data)?;
+    Ok(true)
+}
+
+/// Record the current contents of the entry file at `path`, if it exists.
+pub fn record_file(path: &Path) -> Result<bool, Box<dyn Error>> {
+    let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+        return Ok(false);
+    };
+    match read_entry(path) {
+        Ok(content) => record(id, &content),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Write `revision` back to the entry at `path`. The content being replaced
+/// is recorded first so the restore itself can be undone.
+pub fn restore(revision: &Revision, path: &Path) -> Result<(), Box<dyn Error>> {
+    let content = revision.content()?;
+    record_file(path)?;
+    write_entry(path, &content)?;
+    if let Some(id) = path.file_stem().and_then(|s| s.to_str()) {
+        record(id, &content)?;
+    }
+    Ok(())
+}
+
+/// Line diff from `old` to `new`, limited to the changed hunks plus a few
+/// lines of context.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let diff = TextDiff::from_lines(old, new);
+    let mut lines = Vec::new();
+    for (i, group) in diff.grouped_ops(DIFF_CONTEXT).iter().enumerate() {
+        if i > 0 {
+            lines.push(DiffLine {
+                kind: DiffKind::Separator,
+                text: "⋯".to_string(),
+            });
+        }
+        for op in group {
+            for change in diff.iter_changes(op) {
+                let kind = match change.tag() {
+                    ChangeTag::Equal => DiffKind::Context,
+                    ChangeTag::Insert => DiffKind::Added,
+                    ChangeTag::Delete => DiffKind::Removed,
+                };
+                lines.push(DiffLine {
+                    kind,
+                    text: change.value().trim_end_matches(['\r', '\n']).to_string(),
+                });
+            }
+        }
+    }
+    lines
+}
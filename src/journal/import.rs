@@ -0,0 +1,402 @@
+// src/journal/import.rs
+//
+// Bringing entries over from other journaling apps. Each source format is an
+// `Importer` that turns its files into `ImportedEntry`s; `plan` then decides
+// where every entry goes in the active journal and `apply` writes them.
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use clap::ValueEnum;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::journal::{
+    entry::{entry_id, JournalEntry},
+    frontmatter::{self, FrontMatter},
+    history,
+    storage::{free_entry_path, load_entries, read_entry, write_entry},
+    tags,
+};
+
+/// An entry read from another app, not yet written anywhere.
+pub struct ImportedEntry {
+    pub timestamp: NaiveDateTime,
+    /// Only the day is known (daily notes); collisions are checked per day
+    pub date_only: bool,
+    pub meta: FrontMatter,
+    pub body: String,
+    /// Where it came from, for reporting
+    pub source: String,
+}
+
+pub trait Importer {
+    /// Read every entry found at `source` (a file or folder).
+    fn read(&self, source: &Path) -> Result<Vec<ImportedEntry>, Box<dyn Error>>;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// jrnl plain-text journal or `jrnl --export txt` output
+    Jrnl,
+    /// Day One JSON export (the .json file or the unzipped folder)
+    DayOne,
+    /// Obsidian daily notes folder (`YYYY-MM-DD.md`)
+    Obsidian,
+    /// Logseq graph or its `journals` folder (`YYYY_MM_DD.md`)
+    Logseq,
+}
+
+impl ImportFormat {
+    pub fn importer(self) -> Box<dyn Importer> {
+        match self {
+            ImportFormat::Jrnl => Box::new(JrnlImporter),
+            ImportFormat::DayOne => Box::new(DayOneImporter),
+            ImportFormat::Obsidian | ImportFormat::Logseq => Box::new(DailyNotesImporter),
+        }
+    }
+}
+
+/// What to do when an imported entry lands on an existing one.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OnConflict {
+    /// Leave the existing entry alone and drop the imported one
+    Skip,
+    /// Keep both; the imported entry gets the next free time stamp
+    Rename,
+    /// Append the imported text (and tags) to the existing entry
+    Merge,
+}
+
+pub enum Action {
+    Create(PathBuf),
+    Skip(String),
+    Merge(PathBuf),
+}
+
+pub struct Planned {
+    pub entry: ImportedEntry,
+    pub action: Action,
+}
+
+// Title and tags as front matter, `#tags` from the text merged in
+fn meta_with(title: Option<String>, tags: impl IntoIterator<Item = String>) -> FrontMatter {
+    let mut meta = FrontMatter::default();
+    meta.title = title;
+    add_tags(&mut meta, tags);
+    meta
+}
+
+fn add_tags(meta: &mut FrontMatter, tags: impl IntoIterator<Item = String>) {
+    for tag in tags {
+        if !tag.is_empty() && !meta.tags.iter().any(|t| tags::same_tag(t, &tag)) {
+            meta.tags.push(tag);
+        }
+    }
+}
+
+/// jrnl entries start with a `[YYYY-MM-DD HH:MM]` line (brackets optional,
+/// seconds and AM/PM allowed) whose first sentence is the title. `@words`
+/// are tags.
+struct JrnlImporter;
+
+impl Importer for JrnlImporter {
+    fn read(&self, source: &Path) -> Result<Vec<ImportedEntry>, Box<dyn Error>> {
+        let header = Regex::new(
+            r"^\[?(\d{4}-\d{2}-\d{2}[ T]\d{1,2}:\d{2}(?::\d{2})?(?: ?[AaPp][Mm])?)\]?\s*(.*)$",
+        )?;
+        let tag = Regex::new(r"(?:^|\s)@([\w-]+)")?;
+        let text = fs::read_to_string(source)?;
+
+        let mut entries = Vec::new();
+        let mut current: Option<(NaiveDateTime, String, Vec<&str>)> = None;
+        let mut finish = |current: Option<(NaiveDateTime, String, Vec<&str>)>| {
+            let Some((timestamp, first_line, lines)) = current else {
+                return;
+            };
+            let first_line = first_line.trim_end_matches(" *").trim();
+            // jrnl ends the title at the first sentence break
+            let split = first_line
+                .match_indices(['.', '?', '!'])
+                .find(|(i, _)| first_line[i + 1..].starts_with(' ') || i + 1 == first_line.len())
+                .map(|(i, _)| i + 1)
+                .unwrap_or(first_line.len());
+            let (title, rest) = first_line.split_at(split);
+            let mut body = rest.trim().to_string();
+            let text = lines.join("\n");
+            if !text.trim().is_empty() {
+                if !body.is_empty() {
+                    body.push_str("\n\n");
+                }
+                body.push_str(text.trim());
+            }
+            body.push('\n');
+            let tags = tag
+                .captures_iter(&format!("{first_line}\n{body}"))
+                .map(|c| c[1].to_string())
+                .collect::<Vec<_>>();
+            entries.push(ImportedEntry {
+                timestamp,
+                date_only: false,
+                meta: meta_with(Some(title.trim().to_string()).filter(|t| !t.is_empty()), tags),
+                body,
+                source: format!("{} [{}]", source.display(), timestamp.format("%Y-%m-%d %H:%M")),
+            });
+        };
+
+        for line in text.lines() {
+            let parsed = header
+                .captures(line)
+                .and_then(|c| Some((parse_jrnl_time(&c[1])?, c[2].to_string())));
+            match parsed {
+                Some((timestamp, first_line)) => {
+                    finish(current.take());
+                    current = Some((timestamp, first_line, Vec::new()));
+                }
+                // Text before the first entry isn't part of any
+                None => {
+                    if let Some((_, _, lines)) = current.as_mut() {
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+        finish(current);
+        Ok(entries)
+    }
+}
+
+fn parse_jrnl_time(text: &str) -> Option<NaiveDateTime> {
+    let text = text.replace('T', " ").to_uppercase();
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%d %I:%M:%S %p",
+        "%Y-%m-%d %I:%M %p",
+        "%Y-%m-%d %I:%M:%S%p",
+        "%Y-%m-%d %I:%M%p",
+    ]
+    .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&text, format).ok())
+}
+
+#[derive(Deserialize)]
+struct DayOneExport {
+    entries: Vec<DayOneEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DayOneEntry {
+    creation_date: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    starred: bool,
+}
+
+/// Day One's JSON export: `{"entries": [{"creationDate", "text", "tags"}]}`.
+/// The first line of the text becomes the title when it's a heading.
+struct DayOneImporter;
+
+impl Importer for DayOneImporter {
+    fn read(&self, source: &Path) -> Result<Vec<ImportedEntry>, Box<dyn Error>> {
+        let files: Vec<PathBuf> = if source.is_dir() {
+            let mut files: Vec<PathBuf> = fs::read_dir(source)?
+                .flatten()
+                .map(|f| f.path())
+                .filter(|p| p.extension().unwrap_or_default() == "json")
+                .collect();
+            files.sort();
+            files
+        } else {
+            vec![source.to_path_buf()]
+        };
+
+        let mut entries = Vec::new();
+        for file in files {
+            let export: DayOneExport = serde_json::from_str(&fs::read_to_string(&file)?)
+                .map_err(|err| format!("{}: not a Day One export ({})", file.display(), err))?;
+            for entry in export.entries {
+                let timestamp = DateTime::parse_from_rfc3339(&entry.creation_date)
+                    .map_err(|err| format!("{}: bad date {}: {}", file.display(), entry.creation_date, err))?
+                    .with_timezone(&Local)
+                    .naive_local();
+                let body = unescape_day_one(&entry.text);
+                let title = body
+                    .lines()
+                    .next()
+                    .and_then(|line| line.strip_prefix('#'))
+                    .map(|line| line.trim_start_matches('#').trim().to_string());
+                let mut tags = entry.tags;
+                if entry.starred {
+                    tags.push("starred".to_string());
+                }
+                entries.push(ImportedEntry {
+                    timestamp,
+                    date_only: false,
+                    meta: meta_with(title, tags),
+                    body: format!("{}\n", body.trim_end()),
+                    source: format!("{} [{}]", file.display(), entry.creation_date),
+                });
+            }
+        }
+        Ok(entries)
+    }
+}
+
+// Day One backslash-escapes Markdown punctuation in plain text
+fn unescape_day_one(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\'
+            && let Some(&next) = chars.peek()
+            && ".-!()[]#*_+>`{}|".contains(next)
+        {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Obsidian and Logseq keep one Markdown file per day, named after the date.
+/// Front matter is kept; `#tag`, `#[[tag]]` and Logseq `tags::` properties
+/// become tags.
+struct DailyNotesImporter;
+
+impl Importer for DailyNotesImporter {
+    fn read(&self, source: &Path) -> Result<Vec<ImportedEntry>, Box<dyn Error>> {
+        // A Logseq graph keeps its daily notes in `journals/`
+        let dir = if source.join("journals").is_dir() {
+            source.join("journals")
+        } else {
+            source.to_path_buf()
+        };
+        let files: Vec<PathBuf> = if dir.is_dir() {
+            fs::read_dir(&dir)?.flatten().map(|f| f.path()).collect()
+        } else {
+            vec![dir.clone()]
+        };
+        let inline_tag = Regex::new(r"(?:^|\s)#(?:\[\[([^\]]+)\]\]|([\p{L}\p{N}_/-]+))")?;
+        let property = Regex::new(r"(?m)^\s*(?:- )?tags::\s*(.+)$")?;
+
+        let mut entries = Vec::new();
+        for path in files {
+            if path.extension().unwrap_or_default() != "md" {
+                continue;
+            }
+            let Some(date) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|stem| NaiveDate::parse_from_str(&stem.replace('_', "-"), "%Y-%m-%d").ok())
+            else {
+                continue;
+            };
+            let content = fs::read_to_string(&path)?;
+            let (meta, body) = frontmatter::split(&content);
+            let mut meta = meta.unwrap_or_default();
+            let mut tags: Vec<String> = inline_tag
+                .captures_iter(body)
+                .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+                .map(|m| m.as_str().to_string())
+                .filter(|tag| !tag.chars().all(|c| c.is_ascii_digit()))
+                .collect();
+            for c in property.captures_iter(body) {
+                tags.extend(
+                    c[1].split(',')
+                        .map(|t| t.trim().trim_start_matches('#').trim_matches(['[', ']']).to_string()),
+                );
+            }
+            add_tags(&mut meta, tags);
+            entries.push(ImportedEntry {
+                timestamp: date.and_time(NaiveTime::MIN),
+                date_only: true,
+                meta,
+                body: body.to_string(),
+                source: path.display().to_string(),
+            });
+        }
+        entries.sort_by_key(|e| e.timestamp);
+        Ok(entries)
+    }
+}
+
+/// Decide where each entry goes. Timed entries collide with an existing
+/// entry of the same id; daily notes with any entry on their day.
+pub fn plan(mut imported: Vec<ImportedEntry>, on_conflict: OnConflict) -> Result<Vec<Planned>, Box<dyn Error>> {
+    imported.sort_by_key(|e| e.timestamp);
+    let existing = load_entries()?;
+    // Paths taken by this import so far, so two imports never share a file
+    let mut taken: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut planned = Vec::new();
+
+    for entry in imported {
+        let id = entry_id(entry.timestamp);
+        let day = entry.timestamp.date();
+        let collision: Option<&JournalEntry> = if entry.date_only {
+            // Prefer the day's legacy `{date}.md` note, then its latest entry
+            existing
+                .iter()
+                .filter(|e| e.timestamp.date() == day)
+                .max_by_key(|e| (e.id.len() == 10, e.timestamp))
+        } else {
+            existing.iter().find(|e| e.id == id)
+        };
+
+        let action = match (collision, on_conflict) {
+            (None, _) | (Some(_), OnConflict::Rename) => {
+                // Renamed entries keep their date
+                let path = free_entry_path(entry.timestamp, |path| path.exists() || taken.contains(path))
+                    .ok_or_else(|| format!("No free entry name left on {}", day))?;
+                taken.insert(path.clone());
+                Action::Create(path)
+            }
+            (Some(existing), OnConflict::Skip) => Action::Skip(existing.id.clone()),
+            (Some(existing), OnConflict::Merge) => Action::Merge(existing.path.clone()),
+        };
+        planned.push(Planned { entry, action });
+    }
+    Ok(planned)
+}
+
+/// Write planned entries to the journal. Returns how many files changed.
+pub fn apply(planned: &[Planned]) -> Result<usize, Box<dyn Error>> {
+    let mut written = 0;
+    for Planned { entry, action } in planned {
+        match action {
+            Action::Create(path) => {
+                write_entry(path, &frontmatter::with_front_matter(&entry.body, &entry.meta))?;
+                let _ = history::record_file(path);
+                written += 1;
+            }
+            Action::Merge(path) => {
+                let existing = read_entry(path)?;
+                let mut meta = frontmatter::split(&existing).0.unwrap_or_default();
+                add_tags(&mut meta, entry.meta.tags.iter().cloned());
+                let mut content = frontmatter::with_front_matter(&existing, &meta);
+                if !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                content.push('\n');
+                if let Some(title) = &entry.meta.title {
+                    content.push_str(&format!("## {title}\n\n"));
+                }
+                content.push_str(&entry.body);
+                let _ = history::record_file(path);
+                write_entry(path, &content)?;
+                let _ = history::record_file(path);
+                written += 1;
+            }
+            Action::Skip(_) => {}
+        }
+    }
+    Ok(written)
+}
@@ -0,0 +1,273 @@
+// src/journal/index.rs
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::settings::{active_journal, get_config_dir, DEFAULT_JOURNAL},
+    journal::{
+        crypto,
+        entry::{parse_entry_id, JournalEntry, DATE_FORMAT},
+        frontmatter, history,
+        search::Query,
+        storage::read_entry,
+        tags::inline_tags,
+    },
+};
+
+const INDEX_VERSION: u32 = 3;
+
+/// On-disk index of the journal directory so listing and searching don't
+/// have to read every note on startup.
+///
+/// Each entry remembers the mtime and size it was indexed at; `update` only
+/// re-reads files whose stamp changed.
+#[derive(Serialize, Deserialize, Default)]
+pub struct SearchIndex {
+    version: u32,
+    journal_dir: PathBuf,
+    entries: BTreeMap<String, IndexedEntry>,
+    /// token -> ids of entries containing it
+    postings: BTreeMap<String, BTreeSet<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct IndexedEntry {
+    mtime_ns: u64,
+    size: u64,
+    /// Raw front matter block, re-parsed on load
+    front_matter: String,
+    heading: Option<String>,
+    /// Inline `#tags` of the body
+    tags: Vec<String>,
+    tokens: Vec<String>,
+}
+
+/// One index file per journal so switching doesn't force a rebuild.
+pub fn index_path() -> Result<PathBuf, Box<dyn Error>> {
+    let journal = active_journal();
+    let name = if journal == DEFAULT_JOURNAL {
+        "index.json".to_string()
+    } else {
+        format!("index-{journal}.json")
+    };
+    Ok(get_config_dir()?.join(name))
+}
+
+/// Lowercased words of `text`, deduplicated.
+pub fn tokenize(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+pub fn modified_ns(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+impl SearchIndex {
+    /// Load the index for `journal_dir` and bring it up to date with the files on disk.
+    pub fn open(journal_dir: &Path) -> Result<SearchIndex, Box<dyn Error>> {
+        let path = index_path()?;
+        let mut index = fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<SearchIndex>(&data).ok())
+            .filter(|index| index.version == INDEX_VERSION && index.journal_dir == journal_dir)
+            .unwrap_or_else(|| SearchIndex {
+                version: INDEX_VERSION,
+                journal_dir: journal_dir.to_path_buf(),
+                ..Default::default()
+            });
+
+        if index.update()? {
+            // A stale index on disk is only slower, never wrong, so don't fail on write errors
+            let _ = index.save(&path);
+        }
+        Ok(index)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        // The index holds entry text; for encrypted journals it only lives in memory
+        if crypto::is_encrypted_dir(&self.journal_dir) {
+            return Ok(());
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string(self)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// Re-index new or modified files and drop deleted ones. Returns true if anything changed.
+    fn update(&mut self) -> Result<bool, Box<dyn Error>> {
+        let mut seen = BTreeSet::new();
+        let mut changed = false;
+
+        if let Ok(dir) = fs::read_dir(&self.journal_dir) {
+            for file in dir.flatten() {
+                let path = file.path();
+                if path.extension().unwrap_or_default() != "md" {
+                    continue;
+                }
+                let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+                    continue;
+                };
+                if parse_entry_id(id).is_none() {
+                    continue;
+                }
+                let Ok(metadata) = file.metadata() else {
+                    continue;
+                };
+
+                let id = id.to_string();
+                let mtime_ns = modified_ns(&metadata);
+                let fresh = self
+                    .entries
+                    .get(&id)
+                    .is_some_and(|e| e.mtime_ns == mtime_ns && e.size == metadata.len());
+                if !fresh {
+                    let content = read_entry(&path).unwrap_or_default();
+                    // Catch edits made outside the app. Files we haven't indexed
+                    // before are left alone so a first scan doesn't copy the whole
+                    // journal into the history. It's a safety net; don't fail
+                    // indexing over it.
+                    if self.entries.contains_key(&id) {
+                        let _ = history::record(&id, &content);
+                    }
+                    self.insert(&id, mtime_ns, metadata.len(), &content);
+                    changed = true;
+                }
+                seen.insert(id);
+            }
+        }
+
+        let removed: Vec<String> = self
+            .entries
+            .keys()
+            .filter(|id| !seen.contains(*id))
+            .cloned()
+            .collect();
+        for id in removed {
+            self.remove(&id);
+            changed = true;
+        }
+        Ok(changed)
+    }
+
+    fn insert(&mut self, id: &str, mtime_ns: u64, size: u64, content: &str) {
+        self.remove(id);
+
+        let (_, body) = frontmatter::split(content);
+        let front_matter = content[..content.len() - body.len()].to_string();
+        let tokens = tokenize(content);
+        for token in &tokens {
+            self.postings
+                .entry(token.clone())
+                .or_default()
+                .insert(id.to_string());
+        }
+
+        self.entries.insert(
+            id.to_string(),
+            IndexedEntry {
+                mtime_ns,
+                size,
+                front_matter,
+                heading: first_heading(body),
+                tags: inline_tags(body),
+                tokens: tokens.into_iter().collect(),
+            },
+        );
+    }
+
+    fn remove(&mut self, id: &str) {
+        if let Some(old) = self.entries.remove(id) {
+            for token in old.tokens {
+                if let Some(ids) = self.postings.get_mut(&token) {
+                    ids.remove(id);
+                    if ids.is_empty() {
+                        self.postings.remove(&token);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Re-index a single entry after it was edited and return it, or `None`
+    /// if the file no longer exists.
+    pub fn reload(&mut self, id: &str) -> Result<Option<JournalEntry>, Box<dyn Error>> {
+        let path = self.journal_dir.join(format!("{id}.md"));
+        match fs::metadata(&path) {
+            Ok(metadata) => {
+                let content = read_entry(&path)?;
+                let _ = history::record(id, &content);
+                self.insert(id, modified_ns(&metadata), metadata.len(), &content);
+            }
+            Err(_) => self.remove(id),
+        }
+        let _ = self.save(&index_path()?);
+        Ok(self.entries.get(id).and_then(|indexed| self.entry(id, indexed)))
+    }
+
+    /// Entries with metadata only; bodies are loaded on demand.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries
+            .iter()
+            .filter_map(|(id, indexed)| self.entry(id, indexed))
+            .collect()
+    }
+
+    fn entry(&self, id: &str, indexed: &IndexedEntry) -> Option<JournalEntry> {
+        let timestamp = parse_entry_id(id)?;
+        Some(JournalEntry {
+            id: id.to_string(),
+            date: timestamp.format(DATE_FORMAT).to_string(),
+            timestamp,
+            path: self.journal_dir.join(format!("{id}.md")),
+            size: indexed.size,
+            meta: frontmatter::split(&indexed.front_matter).0.unwrap_or_default(),
+            heading: indexed.heading.clone(),
+            inline_tags: indexed.tags.clone(),
+            content: None,
+        })
+    }
+
+    /// Ids that could match `query`, or `None` when the query can't be
+    /// answered from the index (regexes) and every entry must be checked.
+    pub fn candidates(&self, query: &Query) -> Option<BTreeSet<String>> {
+        let words = query.index_words()?;
+        let mut result: Option<BTreeSet<String>> = None;
+        for word in words {
+            // Words match as prefixes so results update while typing
+            let ids: BTreeSet<String> = self
+                .postings
+                .range(word.clone()..)
+                .take_while(|(token, _)| token.starts_with(&word))
+                .flat_map(|(_, ids)| ids.iter().cloned())
+                .collect();
+            result = Some(match result {
+                Some(acc) => acc.intersection(&ids).cloned().collect(),
+                None => ids,
+            });
+        }
+        Some(result.unwrap_or_else(|| self.entries.keys().cloned().collect()))
+    }
+}
+
+pub fn first_heading(body: &str) -> Option<String> {
+    body.lines()
+        .find_map(|l| l.strip_prefix('#'))
+        .map(|h| h.trim_start_matches('#').trim().to_string())
+        .filter(|h| !h.is_empty())
+}
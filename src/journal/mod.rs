@@ -1,4 +1,19 @@
 // src/journal/mod.rs
+pub mod append;
 pub mod commands;
+pub mod crypto;
+pub mod dates;
+pub mod editor;
 pub mod entry;
+pub mod export;
+pub mod frontmatter;
+pub mod git;
+pub mod history;
+pub mod import;
+pub mod index;
+pub mod search;
+pub mod site;
 pub mod storage;
+pub mod tags;
+pub mod template;
+pub mod trash;
@@ -0,0 +1,143 @@
+// src/journal/search.rs
+use regex::{Regex, RegexBuilder};
+
+use crate::journal::{entry::JournalEntry, index::tokenize};
+
+/// A compiled search query.
+///
+/// Plain words must all appear somewhere in the entry (in any order) at
+/// the start of a word, `"quoted text"` must appear as a phrase, and
+/// `/pattern/` is a regex. Matching is case-insensitive unless
+/// `case_sensitive` is set.
+pub struct Query {
+    pub input: String,
+    patterns: Vec<Regex>,
+    highlight: Regex,
+    /// Lowercased words for index lookups; `None` for regex queries
+    words: Option<Vec<String>>,
+}
+
+impl Query {
+    pub fn parse(input: &str, force_regex: bool, case_sensitive: bool) -> Result<Query, regex::Error> {
+        let trimmed = input.trim();
+        let is_regex =
+            force_regex || (trimmed.len() > 2 && trimmed.starts_with('/') && trimmed.ends_with('/'));
+        let mut words = None;
+        let sources: Vec<String> = if force_regex {
+            vec![trimmed.to_string()]
+        } else if is_regex {
+            vec![trimmed[1..trimmed.len() - 1].to_string()]
+        } else {
+            let terms = split_terms(trimmed);
+            words = Some(terms.iter().flat_map(|t| tokenize(t)).collect());
+            terms
+                .into_iter()
+                .map(|term| {
+                    // Let phrases match across line breaks and repeated spaces
+                    let pattern = term
+                        .split_whitespace()
+                        .map(regex::escape)
+                        .collect::<Vec<_>>()
+                        .join(r"\s+");
+                    if term.starts_with(|c: char| c.is_alphanumeric()) {
+                        format!(r"\b{pattern}")
+                    } else {
+                        pattern
+                    }
+                })
+                .collect()
+        };
+
+        let build = |source: &str| {
+            RegexBuilder::new(source)
+                .case_insensitive(!case_sensitive)
+                .build()
+        };
+        let patterns = sources
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| build(s))
+            .collect::<Result<Vec<_>, _>>()?;
+        let highlight = build(
+            &sources
+                .iter()
+                .filter(|s| !s.is_empty())
+                .map(|s| format!("(?:{s})"))
+                .collect::<Vec<_>>()
+                .join("|"),
+        )?;
+
+        Ok(Query {
+            input: trimmed.to_string(),
+            patterns,
+            highlight,
+            words,
+        })
+    }
+
+    pub fn index_words(&self) -> Option<Vec<String>> {
+        self.words.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        self.patterns.iter().all(|p| p.is_match(text))
+    }
+
+    pub fn matches_entry(&self, entry: &JournalEntry) -> bool {
+        self.matches(entry.content())
+    }
+
+    /// Byte ranges of every hit in `line`, for highlighting.
+    pub fn find_ranges(&self, line: &str) -> Vec<(usize, usize)> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        self.highlight
+            .find_iter(line)
+            .filter(|m| !m.is_empty())
+            .map(|m| (m.start(), m.end()))
+            .collect()
+    }
+
+    /// Lines of `text` containing a hit, with their 1-based line numbers.
+    pub fn context_lines<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !self.find_ranges(line).is_empty())
+            .map(|(i, line)| (i + 1, line))
+            .collect()
+    }
+}
+
+// Split on whitespace, keeping "double quoted" phrases together
+fn split_terms(input: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                if !current.trim().is_empty() {
+                    terms.push(current.trim().to_string());
+                }
+                current.clear();
+                in_quotes = !in_quotes;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    terms.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        terms.push(current.trim().to_string());
+    }
+    terms
+}
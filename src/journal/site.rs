@@ -0,0 +1,285 @@
+// src/journal/site.rs
+//
+// Static HTML export. The site is plain files that work straight from disk:
+//
+//     index.html          entries by year and month, plus search
+//     entries/<id>.html   one page per entry with previous/next links
+//     tags/index.html     every tag with its entry count
+//     tags/tag-<tag>.html entries carrying that tag
+//     search.js           search index and the code that queries it
+//     style.css
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::Write as _,
+    fs,
+    path::Path,
+};
+
+use chrono::Datelike;
+use pulldown_cmark::html;
+use serde::Serialize;
+
+use crate::{journal::entry::JournalEntry, ui::markdown};
+
+const STYLE: &str = r#"body { background: #1e1f24; color: #dcdcdc; font: 16px/1.6 system-ui, sans-serif; margin: 0; }
+main { max-width: 46rem; margin: 0 auto; padding: 1rem 1.5rem 3rem; }
+nav.site { background: #26282f; padding: .6rem 1.5rem; }
+nav.site a { margin-right: 1.2rem; }
+a { color: #79b851; text-decoration: none; }
+a:hover { text-decoration: underline; }
+h1, h2, h3 { color: #79b851; line-height: 1.25; }
+h2.year { border-bottom: 1px solid #58769f; padding-bottom: .2rem; }
+.date, .meta { color: #999; font-size: .9em; }
+.tag { color: #ffb74d; margin-right: .5rem; }
+ul.entries { list-style: none; padding-left: 0; }
+ul.entries li { margin: .3rem 0; }
+pre, code { background: #2b2d35; border-radius: 4px; }
+pre { padding: .8rem; overflow-x: auto; }
+code { padding: .1rem .3rem; }
+blockquote { border-left: 3px solid #58769f; margin-left: 0; padding-left: 1rem; color: #bbb; }
+nav.pager { display: flex; justify-content: space-between; border-top: 1px solid #444; margin-top: 2rem; padding-top: 1rem; }
+#search { width: 100%; padding: .5rem; font-size: 1rem; background: #2b2d35; color: inherit; border: 1px solid #58769f; border-radius: 4px; }
+"#;
+
+const SEARCH_SCRIPT: &str = r#"
+(function () {
+  var input = document.getElementById("search");
+  var results = document.getElementById("results");
+  if (!input || !results) return;
+  input.addEventListener("input", function () {
+    var words = input.value.toLowerCase().split(/\s+/).filter(Boolean);
+    results.innerHTML = "";
+    if (!words.length) return;
+    SEARCH_INDEX.filter(function (entry) {
+      var haystack = (entry.title + " " + entry.tags.join(" ") + " " + entry.text).toLowerCase();
+      return words.every(function (word) { return haystack.indexOf(word) !== -1; });
+    }).forEach(function (entry) {
+      var item = document.createElement("li");
+      var link = document.createElement("a");
+      link.href = entry.url;
+      link.textContent = entry.title;
+      var date = document.createElement("span");
+      date.className = "date";
+      date.textContent = " " + entry.date;
+      item.appendChild(link);
+      item.appendChild(date);
+      results.appendChild(item);
+    });
+  });
+})();
+"#;
+
+#[derive(Serialize)]
+struct SearchRecord<'a> {
+    title: String,
+    date: String,
+    url: String,
+    tags: Vec<&'a str>,
+    text: &'a str,
+}
+
+/// Write the site for `entries` (oldest first) into `out_dir`. Returns the
+/// number of tag pages written.
+pub fn export_site(entries: &[JournalEntry], out_dir: &Path) -> Result<usize, Box<dyn Error>> {
+    fs::create_dir_all(out_dir.join("entries"))?;
+    fs::create_dir_all(out_dir.join("tags"))?;
+    fs::write(out_dir.join("style.css"), STYLE)?;
+
+    for (i, entry) in entries.iter().enumerate() {
+        let previous = i.checked_sub(1).and_then(|i| entries.get(i));
+        let next = entries.get(i + 1);
+        let page = entry_page(entry, previous, next);
+        fs::write(out_dir.join("entries").join(format!("{}.html", entry.id)), page)?;
+    }
+
+    fs::write(out_dir.join("index.html"), index_page(entries))?;
+
+    // Keyed by lowercase name so `#Work` and `#work` share a page
+    let mut tags: BTreeMap<String, (String, Vec<&JournalEntry>)> = BTreeMap::new();
+    for entry in entries {
+        for tag in entry.tags() {
+            tags.entry(tag.to_lowercase())
+                .or_insert_with(|| (tag.to_string(), Vec::new()))
+                .1
+                .push(entry);
+        }
+    }
+    for (tag, tagged) in tags.values() {
+        let mut body = format!("<h1>Tagged <span class=\"tag\">#{}</span></h1>\n", escape(tag));
+        body.push_str(&entry_list(tagged.iter().rev().copied(), "../"));
+        fs::write(
+            out_dir.join("tags").join(format!("{}.html", slug(tag))),
+            page(&format!("#{tag}"), "../", &body),
+        )?;
+    }
+    fs::write(out_dir.join("tags").join("index.html"), tags_page(&tags))?;
+
+    let records: Vec<SearchRecord> = entries
+        .iter()
+        .map(|entry| SearchRecord {
+            title: title(entry),
+            date: entry.date_label(),
+            url: format!("entries/{}.html", entry.id),
+            tags: entry.tags(),
+            text: entry.body(),
+        })
+        .collect();
+    fs::write(
+        out_dir.join("search.js"),
+        format!("var SEARCH_INDEX = {};\n{}", serde_json::to_string(&records)?, SEARCH_SCRIPT),
+    )?;
+    Ok(tags.len())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// File name for a tag page. Distinct tags get distinct names: `/` becomes
+// `__` and other characters apart from letters, digits and `-` are written as
+// `_<hex>_`, so `#work/meetings`, `#work-meetings` and `#work_meetings` don't
+// collide. The `tag-` prefix keeps `#index` off the tag overview.
+fn slug(tag: &str) -> String {
+    let mut slug = String::from("tag-");
+    for c in tag.to_lowercase().chars() {
+        match c {
+            '/' => slug.push_str("__"),
+            c if c.is_alphanumeric() || c == '-' => slug.push(c),
+            c => {
+                let _ = write!(slug, "_{:x}_", c as u32);
+            }
+        }
+    }
+    slug
+}
+
+fn title(entry: &JournalEntry) -> String {
+    entry.title().filter(|t| !t.trim().is_empty()).unwrap_or_else(|| entry.date_label())
+}
+
+// Wrap `body` in the shared layout; `root` leads back to the site root
+fn page(title: &str, root: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<link rel=\"stylesheet\" href=\"{root}style.css\">\n</head>\n<body>\n\
+         <nav class=\"site\"><a href=\"{root}index.html\">Journal</a><a href=\"{root}tags/index.html\">Tags</a></nav>\n\
+         <main>\n{body}</main>\n</body>\n</html>\n",
+        title = escape(title),
+    )
+}
+
+fn tag_links(tags: &[&str], root: &str) -> String {
+    tags.iter()
+        .map(|tag| {
+            format!(
+                "<a class=\"tag\" href=\"{root}tags/{}.html\">#{}</a>",
+                slug(tag),
+                escape(tag)
+            )
+        })
+        .collect()
+}
+
+// Entries as a list of links, in the order given
+fn entry_list<'a>(entries: impl Iterator<Item = &'a JournalEntry>, root: &str) -> String {
+    let mut html = String::from("<ul class=\"entries\">\n");
+    for entry in entries {
+        let _ = writeln!(
+            html,
+            "<li><a href=\"{root}entries/{}.html\">{}</a> <span class=\"date\">{}</span> {}</li>",
+            entry.id,
+            escape(&title(entry)),
+            entry.date_label(),
+            tag_links(&entry.tags(), root)
+        );
+    }
+    html.push_str("</ul>\n");
+    html
+}
+
+fn index_page(entries: &[JournalEntry]) -> String {
+    let mut body = String::from(
+        "<h1>Journal</h1>\n<input id=\"search\" type=\"search\" placeholder=\"Search entries…\" autofocus>\n\
+         <ul id=\"results\" class=\"entries\"></ul>\n",
+    );
+    // Newest year and month first
+    let mut months: BTreeMap<(i32, u32), Vec<&JournalEntry>> = BTreeMap::new();
+    for entry in entries {
+        let day = entry.timestamp.date();
+        months.entry((day.year(), day.month())).or_default().push(entry);
+    }
+    let mut year = None;
+    for ((y, _), month_entries) in months.iter().rev() {
+        if year != Some(*y) {
+            let _ = writeln!(body, "<h2 class=\"year\">{y}</h2>");
+            year = Some(*y);
+        }
+        let _ = writeln!(body, "<h3>{}</h3>", month_entries[0].timestamp.format("%B"));
+        body.push_str(&entry_list(month_entries.iter().rev().copied(), ""));
+    }
+    if entries.is_empty() {
+        body.push_str("<p class=\"meta\">No entries.</p>\n");
+    }
+    body.push_str("<script src=\"search.js\"></script>\n");
+    page("Journal", "", &body)
+}
+
+fn tags_page(tags: &BTreeMap<String, (String, Vec<&JournalEntry>)>) -> String {
+    let mut body = String::from("<h1>Tags</h1>\n<ul class=\"entries\">\n");
+    for (tag, tagged) in tags.values() {
+        let _ = writeln!(
+            body,
+            "<li><a class=\"tag\" href=\"{}.html\">#{}</a> <span class=\"date\">{} entries</span></li>",
+            slug(tag),
+            escape(tag),
+            tagged.len()
+        );
+    }
+    body.push_str("</ul>\n");
+    page("Tags", "../", &body)
+}
+
+fn entry_page(entry: &JournalEntry, previous: Option<&JournalEntry>, next: Option<&JournalEntry>) -> String {
+    let title = title(entry);
+    let mut body = String::new();
+    // Without a front matter title the body's own heading already is the title
+    if entry.meta.title.is_some() || entry.heading.is_none() {
+        let _ = writeln!(body, "<h1>{}</h1>", escape(&title));
+    }
+    let _ = write!(body, "<p class=\"meta\">{}", entry.date_label());
+    if let Some(category) = &entry.meta.category {
+        let _ = write!(body, " · {}", escape(category));
+    }
+    if let Some(mood) = &entry.meta.mood {
+        let _ = write!(body, " · mood: {}", escape(mood));
+    }
+    body.push_str("</p>\n");
+    let tags = entry.tags();
+    if !tags.is_empty() {
+        let _ = writeln!(body, "<p>{}</p>", tag_links(&tags, "../"));
+    }
+
+    body.push_str("<article>\n");
+    html::push_html(&mut body, markdown::parser(entry.body()));
+    body.push_str("</article>\n<nav class=\"pager\">");
+    let link = |entry: Option<&JournalEntry>, before: &str, after: &str| match entry {
+        Some(entry) => format!(
+            "<a href=\"{}.html\">{}{}{}</a>",
+            entry.id,
+            before,
+            escape(&self::title(entry)),
+            after
+        ),
+        None => "<span></span>".to_string(),
+    };
+    body.push_str(&link(previous, "← ", ""));
+    body.push_str(&link(next, "", " →"));
+    body.push_str("</nav>\n");
+    page(&title, "../", &body)
+}
@@ -1,54 +1,218 @@
 // src/journal/storage.rs
-use std::{error::Error, fs::{self, File}, io::Read};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
 
-use crate::{app::App, config::settings::get_journal_dir, journal::entry::{JournalEntry, StatefulList}};
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
 
+use crate::{
+    app::App,
+    config::settings::get_journal_dir,
+    journal::{
+        crypto,
+        dates::parse_date,
+        entry::{entry_id, parse_entry_id, JournalEntry, DATE_FORMAT},
+        frontmatter::{self, FrontMatter},
+        index::SearchIndex,
+    },
+};
+
+pub fn load_index() -> Result<SearchIndex, Box<dyn Error>> {
+    // Fail up front on a wrong passphrase rather than indexing empty entries
+    crypto::unlock()?;
+    SearchIndex::open(&get_journal_dir())
+}
+
+/// All entries with their metadata, newest first. Bodies are not loaded;
+/// call `load_content` for the ones you need.
 pub fn load_entries() -> Result<Vec<JournalEntry>, Box<dyn Error>> {
-    let journal_dir = get_journal_dir();
-    let mut entries = Vec::new();
-
-    if let Ok(entries_iter) = fs::read_dir(&journal_dir) {
-        for entry in entries_iter {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                
-                // Only process .md files
-                if path.extension().unwrap_or_default() == "md" {
-                    if let Some(name) = path.file_name() {
-                        if let Some(name_str) = name.to_str() {
-                            let date = name_str.replace(".md", "");
-                            
-                            // Get file metadata
-                            if let Ok(metadata) = fs::metadata(&path) {
-                                let size = metadata.len();
-                                
-                                // Read content
-                                let mut content = String::new();
-                                if let Ok(mut file) = File::open(&path) {
-                                    let _ = file.read_to_string(&mut content);
-                                }
-                                
-                                entries.push(JournalEntry {
-                                    date,
-                                    size,
-                                    content,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
+    let mut entries = load_index()?.entries();
+    sort_entries(&mut entries);
+    Ok(entries)
+}
+
+pub fn sort_entries(entries: &mut [JournalEntry]) {
+    // Sort entries by time (newest first)
+    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| b.id.cmp(&a.id)));
+}
+
+/// Read an entry file, decrypting it if the journal is encrypted.
+pub fn read_entry(path: &Path) -> Result<String, Box<dyn Error>> {
+    crypto::decrypt_text(&fs::read_to_string(path)?)
+}
+
+/// Write an entry file, encrypting it if the journal is encrypted.
+pub fn write_entry(path: &Path, content: &str) -> Result<(), Box<dyn Error>> {
+    if crypto::is_encrypted() {
+        fs::write(path, crypto::encrypt_text(content)?)?;
+    } else {
+        fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+/// Read an entry's body from disk if it hasn't been loaded yet.
+pub fn load_content(entry: &mut JournalEntry) {
+    if entry.content.is_none() {
+        entry.content = Some(read_entry(&entry.path).unwrap_or_default());
+    }
+}
+
+pub fn entry_path(id: &str) -> PathBuf {
+    get_journal_dir().join(format!("{id}.md"))
+}
+
+/// Pick a path for a brand new note at `timestamp`, moving to the next free
+/// second if a note already exists with that name. The note stays on the same
+/// day: near midnight it takes an earlier free second instead.
+pub fn new_entry_path(timestamp: NaiveDateTime) -> PathBuf {
+    free_entry_path(timestamp, |path| path.exists())
+        // Every second of the day is taken; open the existing note instead
+        .unwrap_or_else(|| entry_path(&entry_id(timestamp)))
+}
+
+/// The path for `timestamp`, or the nearest one on the same day for which
+/// `taken` is false, searching later seconds first. `None` if the whole day
+/// is taken.
+pub fn free_entry_path(timestamp: NaiveDateTime, taken: impl Fn(&Path) -> bool) -> Option<PathBuf> {
+    let day = timestamp.date();
+    let second = chrono::Duration::seconds(1);
+    let later = std::iter::successors(Some(timestamp), |t| Some(*t + second));
+    let earlier = std::iter::successors(Some(timestamp - second), |t| Some(*t - second));
+    later
+        .take_while(|t| t.date() == day)
+        .chain(earlier.take_while(|t| t.date() == day))
+        .map(|t| entry_path(&entry_id(t)))
+        .find(|path| !taken(path))
+}
+
+/// The most recent note for `date`, or a path for a new one if the day is
+/// empty (stamped now for today, midnight otherwise).
+pub fn path_for_date(date: NaiveDate) -> Result<PathBuf, Box<dyn Error>> {
+    let day = date.format(DATE_FORMAT).to_string();
+    if let Some(entry) = load_entries()?.into_iter().find(|e| e.date == day) {
+        return Ok(entry.path);
+    }
+    Ok(new_entry_path(timestamp_for_date(date)))
+}
+
+/// Timestamp for a new entry on `date`: now for today, midnight otherwise.
+pub fn timestamp_for_date(date: NaiveDate) -> NaiveDateTime {
+    let now = Local::now();
+    let time = if date == now.date_naive() {
+        now.time()
+    } else {
+        NaiveTime::MIN
+    };
+    date.and_time(time)
+}
+
+/// What a CLI argument names: every entry written on a day, or one entry.
+pub enum EntryTarget {
+    Day(NaiveDate),
+    Id(String),
+}
+
+impl EntryTarget {
+    /// Parse an entry id or a date in any form `parse_date` understands.
+    /// Anything else is an error, so a typo or a path like `../notes` never
+    /// reaches the file system.
+    pub fn parse(input: &str) -> Result<EntryTarget, Box<dyn Error>> {
+        let input = input.trim();
+        let name = input.strip_suffix(".md").unwrap_or(input);
+        if name.contains('_')
+            && let Some(timestamp) = parse_entry_id(name)
+        {
+            return Ok(EntryTarget::Id(entry_id(timestamp)));
+        }
+        match parse_date(input, Local::now().date_naive()) {
+            Ok(date) => Ok(EntryTarget::Day(date)),
+            Err(_) if input.contains(['/', '\\']) || input.contains("..") => Err(format!(
+                "`{}` looks like a path; pass a date or an entry id instead",
+                input
+            )
+            .into()),
+            Err(err) => Err(err.into()),
         }
     }
-    
-    // Sort entries by date (newest first)
-    entries.sort_by(|a, b| b.date.cmp(&a.date));
-    
-    Ok(entries)
+
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            EntryTarget::Day(date) => *date,
+            EntryTarget::Id(id) => parse_entry_id(id).map(|t| t.date()).unwrap_or_default(),
+        }
+    }
+
+    fn matches(&self, entry: &JournalEntry) -> bool {
+        match self {
+            EntryTarget::Day(date) => entry.timestamp.date() == *date,
+            EntryTarget::Id(id) => entry.id == *id,
+        }
+    }
+}
+
+impl fmt::Display for EntryTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryTarget::Day(date) => write!(f, "{}", date.format(DATE_FORMAT)),
+            EntryTarget::Id(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+/// Entries named by `target`, with their content loaded.
+pub fn find_entries(target: &EntryTarget) -> Result<Vec<JournalEntry>, Box<dyn Error>> {
+    let mut matches: Vec<JournalEntry> = load_entries()?
+        .into_iter()
+        .filter(|e| target.matches(e))
+        .collect();
+    matches.iter_mut().for_each(load_content);
+    Ok(matches)
+}
+
+/// Up to `count` days with entries closest to `date`, nearest first, with the
+/// number of entries on each.
+pub fn nearest_days(date: NaiveDate, count: usize) -> Result<Vec<(NaiveDate, usize)>, Box<dyn Error>> {
+    let mut days: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for entry in load_entries()? {
+        *days.entry(entry.timestamp.date()).or_default() += 1;
+    }
+    let mut days: Vec<(NaiveDate, usize)> = days.into_iter().collect();
+    days.sort_by_key(|(day, _)| ((*day - date).num_days().abs(), *day));
+    days.truncate(count);
+    Ok(days)
+}
+
+/// Every tag used in `entries` (front matter or inline) with the number of
+/// entries carrying it, most used first. Spellings that differ only in case
+/// count as one tag, shown as first seen.
+pub fn tag_index(entries: &[JournalEntry]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    for entry in entries {
+        for tag in entry.tags() {
+            counts.entry(tag.to_lowercase()).or_insert_with(|| (tag.to_string(), 0)).1 += 1;
+        }
+    }
+    let mut tags: Vec<(String, usize)> = counts.into_values().collect();
+    tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.to_lowercase().cmp(&b.0.to_lowercase())));
+    tags
+}
+
+/// Rewrite only the front matter of an entry; the body and any keys we
+/// don't understand are written back unchanged.
+pub fn write_entry_meta(entry: &JournalEntry, meta: &FrontMatter) -> Result<(), Box<dyn Error>> {
+    let updated = frontmatter::with_front_matter(entry.content(), meta);
+    write_entry(&entry.path, &updated)
 }
 
 pub fn refresh_entries(app: &mut App) -> Result<(), Box<dyn Error>> {
-    let entries = load_entries()?;
-    app.entries_list = StatefulList::with_items(entries);
+    let index = load_index()?;
+    let mut entries = index.entries();
+    sort_entries(&mut entries);
+    app.index = index;
+    app.set_entries(entries);
     Ok(())
 }
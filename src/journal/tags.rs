@@ -0,0 +1,113 @@
+// src/journal/tags.rs
+//
+// Inline `#tags` in entry bodies, and renaming tags across a whole entry.
+// A tag starts with a letter or `_` and may contain letters, digits, `_`, `-`
+// and `/` (for nested tags like `#work/meetings`). Tags inside code, in
+// headings, URLs and `[link](#anchors)` don't count.
+use std::sync::OnceLock;
+
+use regex::{Captures, Regex};
+
+use crate::journal::frontmatter;
+
+fn tag_pattern() -> &'static Regex {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    TAG.get_or_init(|| Regex::new(r"(^|[^\w&#/])#([\p{L}_][\p{L}\p{N}_/-]*)").expect("valid regex"))
+}
+
+/// Tags compare case-insensitively.
+pub fn same_tag(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+/// `#tags` written in `body`, in order of first appearance.
+pub fn inline_tags(body: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for_each_text(body, |text| {
+        for caps in tag_pattern().captures_iter(text) {
+            if let Some(tag) = tag_in(text, &caps)
+                && !tags.iter().any(|t| same_tag(t, tag))
+            {
+                tags.push(tag.to_string());
+            }
+        }
+        text.to_string()
+    });
+    tags
+}
+
+/// `content` with every tag in `from` replaced by `to`, both in the front
+/// matter and inline. Returns `None` if nothing changed.
+pub fn rename(content: &str, from: &[String], to: &str) -> Option<String> {
+    let renamed = |tag: &str| from.iter().any(|f| same_tag(f, tag));
+    let (meta, body) = frontmatter::split(content);
+    let front = &content[..content.len() - body.len()];
+
+    let mut changed = false;
+    let body = for_each_text(body, |text| {
+        tag_pattern().replace_all(text, |caps: &Captures| match tag_in(text, caps) {
+            Some(tag) if renamed(tag) => {
+                changed = true;
+                format!("{}#{}{}", &caps[1], to, &caps[2][tag.len()..])
+            }
+            _ => caps[0].to_string(),
+        })
+        .into_owned()
+    });
+
+    let mut content = format!("{front}{body}");
+    if let Some(mut meta) = meta
+        && meta.tags.iter().any(|t| renamed(t))
+    {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in &meta.tags {
+            let tag = if renamed(tag) { to } else { tag.as_str() };
+            if !tags.iter().any(|t| same_tag(t, tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        meta.tags = tags;
+        content = frontmatter::with_front_matter(&content, &meta);
+        changed = true;
+    }
+    changed.then_some(content)
+}
+
+// The tag matched by `caps`, or `None` for a link anchor
+fn tag_in<'a>(text: &str, caps: &Captures<'a>) -> Option<&'a str> {
+    let prefix = caps.get(1)?;
+    if prefix.as_str() == "(" && text[..prefix.start()].ends_with(']') {
+        return None;
+    }
+    let tag = caps.get(2)?.as_str().trim_end_matches(['-', '/']);
+    (!tag.is_empty()).then_some(tag)
+}
+
+// Run `f` over the prose of `body` (outside code blocks and inline code),
+// putting its result in place of each piece
+fn for_each_text(body: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut in_code = false;
+    for line in body.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+        }
+        if in_code || trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            out.push_str(line);
+            continue;
+        }
+        // Odd pieces between backticks are inline code
+        for (i, piece) in line.split('`').enumerate() {
+            if i > 0 {
+                out.push('`');
+            }
+            if i % 2 == 0 {
+                out.push_str(&f(piece));
+            } else {
+                out.push_str(piece);
+            }
+        }
+    }
+    out
+}
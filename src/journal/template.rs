@@ -0,0 +1,169 @@
+// src/journal/template.rs
+//
+// Templates for new entries live in `<config dir>/templates/<name>.md`.
+// Variables in braces are filled in when an entry is created:
+//
+//     {date} {date:%A, %B %e}   entry date, optionally in a chrono format
+//     {time} {time:%I:%M %p}    entry time
+//     {weekday} {week} {year}   weekday name, ISO week number, year
+//     {journal}                 name of the journal
+//     {yesterday_tasks}         open `- [ ]` tasks from the previous day with entries
+//     {prompt}                  a random writing prompt (from `prompts.txt` if present)
+//
+// Unknown variables are left as they are.
+use std::{
+    error::Error,
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{Datelike, NaiveDateTime};
+use regex::{Captures, Regex};
+
+use crate::{
+    config::settings::{active_journal, get_config_dir, get_template_name, is_template_name},
+    journal::{
+        entry::DATE_FORMAT,
+        storage::{load_content, load_entries},
+    },
+};
+
+/// Used when no template is configured.
+pub const BUILTIN_TEMPLATE: &str =
+    "---\ntitle: \ntags: []\ncategory: \nmood: \n---\n\n# Journal Entry: {date}\n\n";
+
+const PROMPTS: [&str; 12] = [
+    "What made you smile today?",
+    "What is one thing you learned recently?",
+    "What are you looking forward to this week?",
+    "Describe a small win from today.",
+    "What has been on your mind lately?",
+    "Who helped you recently, and how?",
+    "What would make tomorrow great?",
+    "What drained your energy today, and what restored it?",
+    "What are you grateful for right now?",
+    "What decision are you putting off?",
+    "Describe today in three words, then explain them.",
+    "What would you tell yourself a year ago?",
+];
+
+pub fn templates_dir() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(get_config_dir()?.join("templates"))
+}
+
+/// Names of the user's templates, sorted.
+pub fn template_names() -> Vec<String> {
+    let Ok(files) = templates_dir().and_then(|dir| Ok(fs::read_dir(dir)?)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = files
+        .flatten()
+        .map(|f| f.path())
+        .filter(|path| path.extension().unwrap_or_default() == "md")
+        .filter_map(|path| path.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Text of template `name`, or of the journal's default template (falling
+/// back to the built-in one) when `name` is `None`.
+pub fn load_template(name: Option<&str>) -> Result<String, Box<dyn Error>> {
+    let Some(name) = name.map(str::to_string).or_else(get_template_name) else {
+        return Ok(BUILTIN_TEMPLATE.to_string());
+    };
+    if !is_template_name(&name) {
+        return Err(format!("`{}` is not a template name", name).into());
+    }
+    let path = templates_dir()?.join(format!("{name}.md"));
+    fs::read_to_string(&path).map_err(|err| {
+        let names = template_names();
+        let available = if names.is_empty() {
+            format!("no templates in {}", templates_dir().unwrap_or_default().display())
+        } else {
+            format!("available: {}", names.join(", "))
+        };
+        format!("Template {} ({}): {}", name, available, err).into()
+    })
+}
+
+/// Fill in the variables of `template` for an entry created at `timestamp`.
+pub fn render(template: &str, timestamp: NaiveDateTime) -> String {
+    let variable = Regex::new(r"\{(\w+)(?::([^{}]*))?\}").expect("valid regex");
+    variable
+        .replace_all(template, |caps: &Captures| {
+            let format = caps.get(2).map(|m| m.as_str());
+            match (&caps[1], format) {
+                ("date", None) => timestamp.format(DATE_FORMAT).to_string(),
+                ("time", None) => timestamp.format("%H:%M").to_string(),
+                ("date" | "time", Some(format)) => format_timestamp(timestamp, format)
+                    .unwrap_or_else(|| caps[0].to_string()),
+                ("weekday", None) => timestamp.format("%A").to_string(),
+                ("week", None) => timestamp.iso_week().week().to_string(),
+                ("year", None) => timestamp.year().to_string(),
+                ("journal", None) => active_journal(),
+                ("yesterday_tasks", None) => open_tasks_before(timestamp).join("\n"),
+                ("prompt", None) => random_prompt(),
+                _ => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+// chrono panics while printing an invalid format, so check it first
+fn format_timestamp(timestamp: NaiveDateTime, format: &str) -> Option<String> {
+    use chrono::format::{Item, StrftimeItems};
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return None;
+    }
+    Some(timestamp.format(format).to_string())
+}
+
+// Unchecked tasks from the last day before `timestamp` that has entries
+fn open_tasks_before(timestamp: NaiveDateTime) -> Vec<String> {
+    let Ok(entries) = load_entries() else {
+        return Vec::new();
+    };
+    let day = timestamp.date();
+    let Some(previous) = entries.iter().map(|e| e.timestamp.date()).filter(|d| *d < day).max() else {
+        return Vec::new();
+    };
+    let mut tasks = Vec::new();
+    // Entries come newest first; keep the day's tasks in writing order
+    for mut entry in entries.into_iter().filter(|e| e.timestamp.date() == previous).rev() {
+        load_content(&mut entry);
+        tasks.extend(
+            entry
+                .body()
+                .lines()
+                .map(str::trim_start)
+                .filter(|line| ["- [ ] ", "* [ ] ", "+ [ ] "].iter().any(|p| line.starts_with(p)))
+                .map(|line| format!("- [ ] {}", &line[6..])),
+        );
+    }
+    tasks
+}
+
+fn random_prompt() -> String {
+    let custom: Vec<String> = get_config_dir()
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join("prompts.txt")).ok())
+        .map(|text| {
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as usize)
+        .unwrap_or(0);
+    if custom.is_empty() {
+        PROMPTS[seed % PROMPTS.len()].to_string()
+    } else {
+        custom[seed % custom.len()].clone()
+    }
+}
@@ -0,0 +1,178 @@
+// src/journal/trash.rs
+//
+// Deleted entries are moved to `<journal>/.trash` instead of being removed.
+// Each one is kept as `<name>.md` next to a `<name>.json` recording where it
+// came from and when it was deleted. `name` is the entry id, with a `~N`
+// suffix when the same id was deleted more than once.
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Duration, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::config::settings::{config, get_journal_dir};
+
+const DELETED_AT_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+#[derive(Serialize, Deserialize)]
+struct TrashMeta {
+    id: String,
+    deleted_at: String,
+    original_path: PathBuf,
+}
+
+#[derive(Clone)]
+pub struct TrashItem {
+    /// File stem inside the trash, used to restore it
+    pub name: String,
+    pub id: String,
+    pub deleted_at: NaiveDateTime,
+    pub original_path: PathBuf,
+    pub path: PathBuf,
+}
+
+impl TrashItem {
+    /// When the retention period runs out, if one is configured.
+    pub fn expires_at(&self) -> Option<NaiveDateTime> {
+        let days = config().trash_retention_days;
+        (days > 0).then(|| self.deleted_at + Duration::days(days as i64))
+    }
+
+    fn meta_path(&self) -> PathBuf {
+        self.path.with_extension("json")
+    }
+}
+
+pub fn trash_dir() -> PathBuf {
+    get_journal_dir().join(".trash")
+}
+
+/// Move the entry at `path` into the trash.
+pub fn move_to_trash(path: &Path) -> Result<TrashItem, Box<dyn Error>> {
+    let id = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| format!("{} is not an entry", path.display()))?
+        .to_string();
+    let dir = trash_dir();
+    fs::create_dir_all(&dir)?;
+
+    let mut name = id.clone();
+    let mut n = 1;
+    while dir.join(format!("{name}.md")).exists() {
+        n += 1;
+        name = format!("{id}~{n}");
+    }
+
+    let item = TrashItem {
+        path: dir.join(format!("{name}.md")),
+        name,
+        id,
+        deleted_at: Local::now().naive_local(),
+        original_path: path.to_path_buf(),
+    };
+    let meta = TrashMeta {
+        id: item.id.clone(),
+        deleted_at: item.deleted_at.format(DELETED_AT_FORMAT).to_string(),
+        original_path: item.original_path.clone(),
+    };
+    fs::write(item.meta_path(), serde_json::to_string_pretty(&meta)?)?;
+    fs::rename(path, &item.path)?;
+    Ok(item)
+}
+
+/// Everything in the trash, most recently deleted first.
+pub fn list_trash() -> Result<Vec<TrashItem>, Box<dyn Error>> {
+    let dir = trash_dir();
+    let mut items = Vec::new();
+    let Ok(files) = fs::read_dir(&dir) else {
+        return Ok(items);
+    };
+    for file in files.flatten() {
+        let path = file.path();
+        if path.extension().unwrap_or_default() != "md" {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        // Without its metadata we don't know where an item belongs; leave it be
+        let Some(meta) = fs::read_to_string(path.with_extension("json"))
+            .ok()
+            .and_then(|data| serde_json::from_str::<TrashMeta>(&data).ok())
+        else {
+            continue;
+        };
+        let Ok(deleted_at) = NaiveDateTime::parse_from_str(&meta.deleted_at, DELETED_AT_FORMAT) else {
+            continue;
+        };
+        items.push(TrashItem {
+            name: name.to_string(),
+            id: meta.id,
+            deleted_at,
+            original_path: meta.original_path,
+            path,
+        });
+    }
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then_with(|| b.name.cmp(&a.name)));
+    Ok(items)
+}
+
+/// Find a trashed entry by its trash name or entry id (the latest deletion wins).
+pub fn find_in_trash(target: &str) -> Result<Option<TrashItem>, Box<dyn Error>> {
+    let items = list_trash()?;
+    Ok(items
+        .iter()
+        .find(|item| item.name == target)
+        .or_else(|| items.iter().find(|item| item.id == target))
+        .cloned())
+}
+
+/// Put a trashed entry back where it was deleted from.
+pub fn restore(item: &TrashItem) -> Result<PathBuf, Box<dyn Error>> {
+    if item.original_path.exists() {
+        return Err(format!(
+            "an entry already exists at {}; move it away first",
+            item.original_path.display()
+        )
+        .into());
+    }
+    if let Some(parent) = item.original_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&item.path, &item.original_path)?;
+    let _ = fs::remove_file(item.meta_path());
+    Ok(item.original_path.clone())
+}
+
+/// Permanently delete one trashed entry.
+pub fn purge(item: &TrashItem) -> Result<(), Box<dyn Error>> {
+    fs::remove_file(&item.path)?;
+    let _ = fs::remove_file(item.meta_path());
+    Ok(())
+}
+
+/// Permanently delete everything in the trash. Returns how many entries went.
+pub fn empty_trash() -> Result<usize, Box<dyn Error>> {
+    let items = list_trash()?;
+    for item in &items {
+        purge(item)?;
+    }
+    Ok(items.len())
+}
+
+/// Drop entries that have been in the trash longer than `trash_retention_days`.
+pub fn purge_expired() -> Result<usize, Box<dyn Error>> {
+    let now = Local::now().naive_local();
+    let mut purged = 0;
+    for item in list_trash()? {
+        if item.expires_at().is_some_and(|expires| expires <= now) {
+            purge(&item)?;
+            purged += 1;
+        }
+    }
+    Ok(purged)
+}
@@ -1,13 +1,18 @@
 // src/main.rs
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use colored::*;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture},
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::{error::Error, fs, io};
+use std::{
+    error::Error,
+    fs,
+    io::{self, IsTerminal},
+    path::PathBuf,
+};
 
 mod app;
 mod config;
@@ -15,9 +20,23 @@ mod journal;
 mod ui;
 
 use app::App;
-use chrono::Local;
-use config::settings::{get_config_path, get_journal_dir};
-use journal::commands::{delete_entry_cli, display_entries_list, open_editor, view_entry_cli};
+use chrono::{Local, NaiveDate};
+use config::settings::{get_journal_dir, init_config, PathOverrides};
+use journal::commands::{
+    add_cli, config_cli, decrypt_cli, delete_entry_cli, display_entries_list, encrypt_cli,
+    export_bundle_cli, export_html_cli, history_cli, import_cli, journals_create_cli,
+    journals_list_cli, journals_remove_cli, meta_entry_cli, new_entry_at, passwd_cli, search_cli,
+    sync_cli, tags_list_cli, tags_rename_cli, trash_empty_cli, trash_list_cli, trash_restore_cli,
+    view_entry_cli,
+};
+use journal::{
+    crypto,
+    dates::parse_date,
+    export::{ExportFilter, ExportFormat},
+    import::{ImportFormat, OnConflict},
+    storage::timestamp_for_date,
+    trash::purge_expired,
+};
 
 #[derive(Parser)]
 #[command(name = "termjournal")]
@@ -26,96 +45,439 @@ use journal::commands::{delete_entry_cli, display_entries_list, open_editor, vie
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// Journal directory to use (overrides $NOTES_DIR and the config file)
+    #[arg(long, global = true, value_name = "DIR")]
+    journal_dir: Option<PathBuf>,
+
+    /// Config file to use (overrides $NOTES_CONFIG)
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Named journal to use (see `journals`)
+    #[arg(short, long, global = true, value_name = "NAME")]
+    journal: Option<String>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Create a new journal entry
-    New,
+    New {
+        /// Template to start from, by name in `<config dir>/templates`
+        #[arg(short, long)]
+        template: Option<String>,
+
+        /// Day to write about: 2024-05-01, yesterday, last friday, 3 days ago, ...
+        #[arg(short, long)]
+        date: Option<String>,
+    },
+
+    /// Append a timestamped bullet to a day's entry without opening an editor
+    Add {
+        /// Text to add; read from stdin when left out
+        text: Vec<String>,
+
+        /// Day to add to: 2024-05-01, yesterday, ... (defaults to today)
+        #[arg(short, long)]
+        date: Option<String>,
+
+        /// Tag the entry (repeat for several)
+        #[arg(short, long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Heading to add under; appended to the entry if it doesn't exist
+        #[arg(short, long, value_name = "HEADING")]
+        section: Option<String>,
+    },
 
     /// View a journal entry
     View {
-        /// Date in YYYY-MM-DD format (defaults to today)
+        /// Date (2024-05-01, yesterday, last friday, ...) to show every entry that day, or an exact entry id (defaults to today)
         date: Option<String>,
     },
 
     /// Delete a journal entry
     Delete {
-        /// Date in YYYY-MM-DD format (defaults to today)
+        /// Date (2024-05-01, yesterday, ...) or an exact entry id (defaults to today)
         date: Option<String>,
     },
 
     /// List all journal entries
-    List,
+    List {
+        /// Only entries with this tag (repeat for any of several)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+    },
+
+    /// List tags with their entry counts, or rename and merge them
+    Tags {
+        #[command(subcommand)]
+        action: Option<TagsAction>,
+    },
+
+    /// Search entries: words (all must match), "quoted phrases" or /regex/
+    Search {
+        /// Search query
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// Treat the whole query as a regular expression
+        #[arg(short, long)]
+        regex: bool,
+        /// Match case exactly
+        #[arg(short, long)]
+        case_sensitive: bool,
+    },
+
+    /// Show or edit an entry's front matter (title, tags, category, mood, ...)
+    Meta {
+        /// Date (2024-05-01, yesterday, ...) or an exact entry id
+        entry: String,
+        /// Field to show or set
+        key: Option<String>,
+        /// New value; an empty string clears the field
+        value: Option<String>,
+    },
+
+    /// List, create or remove named journals
+    Journals {
+        #[command(subcommand)]
+        action: Option<JournalsAction>,
+    },
+
+    /// Show an entry's earlier versions, diff against one or restore it
+    History {
+        /// Date (2024-05-01, yesterday, ...) or an exact entry id
+        entry: String,
+        /// Show the changes between revision N and the current entry
+        #[arg(long, value_name = "N", conflicts_with = "restore")]
+        diff: Option<usize>,
+        /// Replace the entry with revision N
+        #[arg(long, value_name = "N")]
+        restore: Option<usize>,
+    },
+
+    /// Pull and push the journal's git repository (see `git.enabled`)
+    Sync,
 
-    /// Configure your journal settings
+    /// Export entries to one Markdown, JSON or text file, or to an HTML site
+    #[command(args_conflicts_with_subcommands = true)]
+    Export {
+        #[command(subcommand)]
+        action: Option<ExportAction>,
+        /// Format of the bundle
+        #[arg(short, long, value_enum, default_value = "markdown")]
+        format: ExportFormat,
+        /// File to write (defaults to stdout)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+
+    /// Import entries from jrnl, Day One, Obsidian or Logseq
+    Import {
+        /// Format of the source
+        #[arg(value_enum)]
+        format: ImportFormat,
+        /// File or folder to import
+        source: PathBuf,
+        /// What to do when an entry already exists
+        #[arg(long, value_enum, default_value = "skip")]
+        on_conflict: OnConflict,
+        /// Show what would be imported without writing anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+
+    /// Encrypt every entry of the journal with a passphrase
+    Encrypt,
+
+    /// Turn an encrypted journal back into plain text
+    Decrypt,
+
+    /// Change the passphrase of an encrypted journal
+    Passwd,
+
+    /// List, restore or empty deleted entries
+    Trash {
+        #[command(subcommand)]
+        action: Option<TrashAction>,
+    },
+
+    /// Show or change settings, e.g. `config editor nvim`
     Config {
-        /// Set editor, e.g., nvim, vim, nano, code
-        #[arg(help = "Set editor, e.g., nvim, vim, nano, code")]
-        editor: String,
+        /// Setting to show or change (omit to list all)
+        key: Option<String>,
+        /// New value; an empty string resets the key to its default
+        value: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum JournalsAction {
+    /// List configured journals
+    List,
+
+    /// Add a named journal
+    Create {
+        name: String,
+        /// Directory for its entries (defaults to a folder next to the default journal)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        /// Template name for new entries
+        #[arg(long)]
+        template: Option<String>,
+        /// Editor command for this journal
+        #[arg(long)]
+        editor: Option<String>,
+    },
+
+    /// Remove a journal from the config (its files are kept)
+    Remove { name: String },
+}
+
+#[derive(Subcommand)]
+enum ExportAction {
+    /// Write a static HTML site with an index, tag pages and search
+    Html {
+        /// Directory to write the site to
+        out_dir: PathBuf,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+}
+
+#[derive(Args)]
+struct FilterArgs {
+    /// Only entries on or after this day (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    from: Option<NaiveDate>,
+    /// Only entries on or before this day (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    to: Option<NaiveDate>,
+    /// Only entries with this tag (repeat for any of several)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+}
+
+impl From<FilterArgs> for ExportFilter {
+    fn from(args: FilterArgs) -> ExportFilter {
+        ExportFilter {
+            from: args.from,
+            to: args.to,
+            tags: args.tags,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum TagsAction {
+    /// List tags, most used first
+    List,
+
+    /// Rename a tag in every entry (merges into `new` if it's already used)
+    Rename { old: String, new: String },
+
+    /// Replace several tags with one, e.g. `tags merge job office --into work`
+    Merge {
+        #[arg(required = true)]
+        tags: Vec<String>,
+        /// Tag to keep
+        #[arg(long, value_name = "TAG")]
+        into: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrashAction {
+    /// List deleted entries
+    List,
+
+    /// Put a deleted entry back
+    Restore {
+        /// Entry id or trash name as shown by `trash list`
+        item: String,
     },
+
+    /// Permanently delete everything in the trash
+    Empty,
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
+    let overrides = PathOverrides {
+        journal_dir: cli.journal_dir.clone(),
+        config_path: cli.config.clone(),
+    };
+    if let Err(err) = init_config(overrides, cli.journal.as_deref()) {
+        eprintln!("{}", format!("Error: {}", err).red());
+        std::process::exit(1);
+    }
     let journal_dir = get_journal_dir();
-    fs::create_dir_all(&journal_dir)?;
+    if let Err(err) = fs::create_dir_all(&journal_dir) {
+        eprintln!(
+            "{}",
+            format!("Error: can't create journal directory {}: {}", journal_dir.display(), err).red()
+        );
+        std::process::exit(1);
+    }
 
-    match cli.command {
-        Some(Commands::New) => {
-            let date = Local::now().format("%Y-%m-%d").to_string();
-            let path = journal_dir.join(format!("{date}.md"));
-            open_editor(&path)?;
+    if let Err(err) = purge_expired() {
+        eprintln!("{}", format!("Warning: couldn't purge old trash: {}", err).yellow());
+    }
+
+    let result = match cli.command {
+        None => run_tui(),
+        Some(command) => run_command(command),
+    };
+    if let Err(err) = result {
+        eprintln!("{}", format!("Error: {}", err).red());
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_command(command: Commands) -> Result<(), Box<dyn Error>> {
+    match command {
+        Commands::New { template, date } => {
+            let timestamp = match date {
+                Some(date) => timestamp_for_date(parse_date(&date, Local::now().date_naive())?),
+                None => Local::now().naive_local(),
+            };
+            new_entry_at(timestamp, template.as_deref())?;
+            Ok(())
+        }
+        Commands::Add { text, date, tags, section } => {
+            let today = Local::now().date_naive();
+            let date = match date {
+                Some(date) => parse_date(&date, today)?,
+                None => today,
+            };
+            let text = if !text.is_empty() {
+                text.join(" ")
+            } else if io::stdin().is_terminal() {
+                String::new()
+            } else {
+                io::read_to_string(io::stdin())?
+            };
+            add_cli(&text, date, &tags, section.as_deref())?;
             Ok(())
         }
-        Some(Commands::View { date }) => {
+        Commands::View { date } => {
             let date = date.unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
             view_entry_cli(&date)?;
             Ok(())
         }
-        Some(Commands::Delete { date }) => {
+        Commands::Delete { date } => {
             let date = date.unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
             delete_entry_cli(&date)?;
             Ok(())
         }
-        Some(Commands::List) => {
-            display_entries_list()?;
+        Commands::List { tags } => {
+            display_entries_list(&tags)?;
+            Ok(())
+        }
+        Commands::Tags { action } => {
+            match action.unwrap_or(TagsAction::List) {
+                TagsAction::List => tags_list_cli()?,
+                TagsAction::Rename { old, new } => tags_rename_cli(&[old], &new)?,
+                TagsAction::Merge { tags, into } => tags_rename_cli(&tags, &into)?,
+            }
+            Ok(())
+        }
+        Commands::Search { query, regex, case_sensitive } => {
+            search_cli(&query.join(" "), regex, case_sensitive)?;
+            Ok(())
+        }
+        Commands::Meta { entry, key, value } => {
+            meta_entry_cli(&entry, key.as_deref(), value.as_deref())?;
             Ok(())
         }
-        Some(Commands::Config { editor }) => {
-            let config_path = get_config_path();
-            fs::write(config_path, editor)?;
-            println!("{}", "✓ Editor updated!".green());
+        Commands::Journals { action } => {
+            match action.unwrap_or(JournalsAction::List) {
+                JournalsAction::List => journals_list_cli()?,
+                JournalsAction::Create { name, dir, template, editor } => {
+                    journals_create_cli(&name, dir, template, editor)?
+                }
+                JournalsAction::Remove { name } => journals_remove_cli(&name)?,
+            }
+            Ok(())
+        }
+        Commands::History { entry, diff, restore } => {
+            history_cli(&entry, diff, restore)?;
+            Ok(())
+        }
+        Commands::Sync => {
+            sync_cli()?;
+            Ok(())
+        }
+        Commands::Export { action, format, output, filter } => {
+            match action {
+                Some(ExportAction::Html { out_dir, filter }) => export_html_cli(&out_dir, &filter.into())?,
+                None => export_bundle_cli(format, output.as_deref(), &filter.into())?,
+            }
+            Ok(())
+        }
+        Commands::Import { format, source, on_conflict, dry_run } => {
+            import_cli(format, &source, on_conflict, dry_run)?;
+            Ok(())
+        }
+        Commands::Encrypt => {
+            encrypt_cli()?;
+            Ok(())
+        }
+        Commands::Decrypt => {
+            decrypt_cli()?;
             Ok(())
         }
-        None => {
-            // Initialize terminal
-            enable_raw_mode()?;
-            let mut stdout = io::stdout();
-            execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-            let backend = CrosstermBackend::new(stdout);
-            let mut terminal = Terminal::new(backend)?;
+        Commands::Passwd => {
+            passwd_cli()?;
+            Ok(())
+        }
+        Commands::Trash { action } => {
+            match action.unwrap_or(TrashAction::List) {
+                TrashAction::List => trash_list_cli()?,
+                TrashAction::Restore { item } => trash_restore_cli(&item)?,
+                TrashAction::Empty => trash_empty_cli()?,
+            }
+            Ok(())
+        }
+        Commands::Config { key, value } => {
+            config_cli(key.as_deref(), value.as_deref())?;
+            Ok(())
+        }
+    }
+}
 
-            // Create app state
-            let mut app = App::new()?;
+fn run_tui() -> Result<(), Box<dyn Error>> {
+    // Ask for the passphrase before taking over the screen
+    crypto::unlock()?;
 
-            // Main loop
-            let res = app::run_app(&mut terminal, &mut app);
+    // Initialize terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
 
-            // Restore terminal
-            disable_raw_mode()?;
-            execute!(
-                terminal.backend_mut(),
-                LeaveAlternateScreen,
-                DisableMouseCapture
-            )?;
-            terminal.show_cursor()?;
+    // Create app state
+    let mut app = App::new()?;
 
-            if let Err(err) = res {
-                println!("{}", format!("Error: {}", err).red());
-            }
+    // Main loop
+    let res = app::run_app(&mut terminal, &mut app);
 
-            Ok(())
-        }
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    if let Err(err) = res {
+        println!("{}", format!("Error: {}", err).red());
     }
+
+    Ok(())
 }
@@ -1,36 +1,146 @@
 // src/ui/calendar_tab.rs
+use std::collections::HashMap;
+
+use chrono::{Datelike, Local, NaiveDate, Weekday};
 use ratatui::{
-    layout::Rect,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, BorderType, Borders, Paragraph},
+    widgets::Paragraph,
     Frame,
 };
 
 use crate::app::App;
+use crate::config::settings::{config, WeekStart};
+use crate::ui::util::{
+    themed_block, ACCENT_COLOR, HIGHLIGHT_COLOR, PRIMARY_COLOR, SECONDARY_TEXT, SUBTLE_TEXT,
+    text_color,
+};
+
+const WEEKDAYS: [&str; 7] = ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"];
+
+// Column of `weekday` in the grid for the configured first day of the week
+fn weekday_column(weekday: Weekday, week_start: WeekStart) -> usize {
+    match week_start {
+        WeekStart::Sunday => weekday.num_days_from_sunday() as usize,
+        WeekStart::Monday => weekday.num_days_from_monday() as usize,
+    }
+}
 
-pub fn render(f: &mut Frame, _app: &mut App, area: Rect) {
-    let now = chrono::Local::now();
-    let current_month = now.format("%B %Y").to_string();
-    
-    let calendar = Paragraph::new(Text::from(vec![
+pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
+    let today = Local::now().date_naive();
+    let selected = app.calendar_date;
+    let mut entry_dates: HashMap<&str, usize> = HashMap::new();
+    for entry in &app.entries {
+        *entry_dates.entry(entry.date.as_str()).or_default() += 1;
+    }
+
+    let first = selected.with_day(1).unwrap_or(selected);
+    let week_start = config().week_start;
+    let offset = weekday_column(first.weekday(), week_start);
+    let mut weekdays = WEEKDAYS.to_vec();
+    if week_start == WeekStart::Monday {
+        weekdays.rotate_left(1);
+    }
+    let days = days_in_month(first);
+
+    let mut lines = vec![
         Line::from(vec![
+            Span::styled("◀ ", Style::default().fg(SUBTLE_TEXT)),
             Span::styled(
-                current_month,
+                first.format("%B %Y").to_string(),
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
             ),
+            Span::styled(" ▶", Style::default().fg(SUBTLE_TEXT)),
         ]),
         Line::from(""),
-        Line::from(" Su  Mo  Tu  We  Th  Fr  Sa "),
-        Line::from("────────────────────────────"),
-        // TODO: Implement full calendar rendering
-    ]))
-    .block(
-        Block::default()
-            .title("Calendar View")
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded),
-    );
-    
-    f.render_widget(calendar, area);
+        Line::from(
+            weekdays
+                .iter()
+                .map(|d| Span::styled(format!(" {} ", d), Style::default().fg(SECONDARY_TEXT)))
+                .collect::<Vec<_>>(),
+        ),
+        Line::from(Span::styled("─".repeat(28), Style::default().fg(SUBTLE_TEXT))),
+    ];
+
+    let mut week: Vec<Span> = vec![Span::raw("    "); offset];
+    let mut column = offset;
+    for day in 1..=days {
+        let date = first.with_day(day).unwrap_or(first);
+        let has_entry = entry_dates.contains_key(date.format("%Y-%m-%d").to_string().as_str());
+
+        let mut style = Style::default().fg(text_color());
+        if has_entry {
+            style = style.fg(PRIMARY_COLOR).add_modifier(Modifier::BOLD);
+        }
+        if date == today {
+            style = style.fg(HIGHLIGHT_COLOR).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        }
+        if date == selected {
+            style = style.bg(HIGHLIGHT_COLOR).fg(Color::Black);
+        }
+
+        let marker = if has_entry { "•" } else { " " };
+        week.push(Span::raw(" "));
+        week.push(Span::styled(format!("{:>2}{}", day, marker), style));
+        column += 1;
+
+        if column == 7 {
+            column = 0;
+            lines.push(Line::from(std::mem::take(&mut week)));
+            lines.push(Line::from(""));
+        }
+    }
+    if !week.is_empty() {
+        lines.push(Line::from(week));
+        lines.push(Line::from(""));
+    }
+
+    let summary = match entry_dates.get(selected.format("%Y-%m-%d").to_string().as_str()) {
+        Some(1) => Span::styled("1 entry — Enter to open", Style::default().fg(PRIMARY_COLOR)),
+        Some(count) => Span::styled(
+            format!("{} entries — Enter opens latest", count),
+            Style::default().fg(PRIMARY_COLOR),
+        ),
+        None => Span::styled("No entry — Enter to create", Style::default().fg(SUBTLE_TEXT)),
+    };
+    lines.push(Line::from(vec![
+        Span::styled(
+            selected.format("%A, %B %-d").to_string(),
+            Style::default().fg(text_color()).add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    lines.push(Line::from(summary));
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("h/j/k/l", Style::default().fg(ACCENT_COLOR)),
+        Span::styled(" move  ", Style::default().fg(SUBTLE_TEXT)),
+        Span::styled("[/]", Style::default().fg(ACCENT_COLOR)),
+        Span::styled(" month  ", Style::default().fg(SUBTLE_TEXT)),
+        Span::styled("t", Style::default().fg(ACCENT_COLOR)),
+        Span::styled(" today", Style::default().fg(SUBTLE_TEXT)),
+    ]));
+
+    let block = themed_block("Calendar View");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // Keep the grid compact and centered horizontally
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(34), Constraint::Min(0)])
+        .split(inner);
+
+    let calendar = Paragraph::new(Text::from(lines)).alignment(Alignment::Left);
+    f.render_widget(calendar, columns[1]);
+}
+
+fn days_in_month(first: NaiveDate) -> u32 {
+    let next = if first.month() == 12 {
+        NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+    };
+    next.map(|n| n.signed_duration_since(first).num_days() as u32)
+        .unwrap_or(31)
 }
@@ -0,0 +1,470 @@
+// src/ui/editor_pane.rs
+//
+// Small modal editor for quick edits without leaving the TUI. Normal mode
+// takes vim-style motions (h/j/k/l, w/b, 0/$, gg/G) and edits (x, dd, D, o/O);
+// i/a/I/A/o/O switch to insert mode and Esc goes back. Ctrl-S saves.
+use std::{error::Error, path::PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+use crate::{
+    journal::{
+        entry::JournalEntry,
+        history,
+        storage::{read_entry, write_entry},
+    },
+    ui::util::{text_color, BORDER_COLOR, HIGHLIGHT_COLOR, PRIMARY_COLOR},
+};
+
+const TAB_WIDTH: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EditorMode {
+    Normal,
+    Insert,
+}
+
+/// What the app should do after a key was handled.
+pub enum EditorAction {
+    None,
+    Save,
+    Close,
+    /// Close was requested with unsaved changes; asking again discards them
+    Unsaved,
+}
+
+#[derive(Clone)]
+struct Snapshot {
+    lines: Vec<String>,
+    row: usize,
+    col: usize,
+}
+
+pub struct EditorPane {
+    pub id: String,
+    pub path: PathBuf,
+    pub mode: EditorMode,
+    lines: Vec<String>,
+    /// Contents as last read or written, to tell whether there are changes
+    saved: Vec<String>,
+    trailing_newline: bool,
+    row: usize,
+    /// Cursor column in characters
+    col: usize,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// First key of a two key command (`dd`, `gg`)
+    pending: Option<char>,
+    confirm_close: bool,
+    /// First visible wrapped row
+    scroll: usize,
+}
+
+impl EditorPane {
+    pub fn open(entry: &JournalEntry) -> Result<EditorPane, Box<dyn Error>> {
+        let content = read_entry(&entry.path)?;
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        Ok(EditorPane {
+            id: entry.id.clone(),
+            path: entry.path.clone(),
+            mode: EditorMode::Normal,
+            saved: lines.clone(),
+            lines,
+            trailing_newline: content.is_empty() || content.ends_with('\n'),
+            row: 0,
+            col: 0,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            pending: None,
+            confirm_close: false,
+            scroll: 0,
+        })
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.lines != self.saved
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = self.lines.join("\n");
+        if self.trailing_newline {
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn save(&mut self) -> Result<(), Box<dyn Error>> {
+        let _ = history::record_file(&self.path);
+        write_entry(&self.path, &self.text())?;
+        let _ = history::record_file(&self.path);
+        self.saved = self.lines.clone();
+        Ok(())
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> EditorAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl && key.code == KeyCode::Char('s') {
+            return EditorAction::Save;
+        }
+        let confirm_close = std::mem::take(&mut self.confirm_close);
+        match self.mode {
+            EditorMode::Insert => self.insert_key(key),
+            EditorMode::Normal if ctrl => {
+                if key.code == KeyCode::Char('r') {
+                    self.redo();
+                }
+            }
+            EditorMode::Normal => {
+                if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+                    if !self.is_dirty() || confirm_close {
+                        return EditorAction::Close;
+                    }
+                    self.confirm_close = true;
+                    return EditorAction::Unsaved;
+                }
+                self.normal_key(key);
+            }
+        }
+        EditorAction::None
+    }
+
+    fn insert_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = EditorMode::Normal;
+                // An insert session that changed nothing shouldn't cost an undo step
+                if self.undo.last().is_some_and(|s| s.lines == self.lines) {
+                    self.undo.pop();
+                }
+                self.col = self.col.saturating_sub(1);
+                self.clamp_col();
+            }
+            KeyCode::Char(c) => self.insert_str(&c.to_string()),
+            KeyCode::Tab => self.insert_str(&" ".repeat(TAB_WIDTH)),
+            KeyCode::Enter => {
+                let at = byte_index(&self.lines[self.row], self.col);
+                let rest = self.lines[self.row].split_off(at);
+                self.lines.insert(self.row + 1, rest);
+                self.row += 1;
+                self.col = 0;
+            }
+            KeyCode::Backspace => {
+                if self.col > 0 {
+                    self.col -= 1;
+                    let at = byte_index(&self.lines[self.row], self.col);
+                    self.lines[self.row].remove(at);
+                } else if self.row > 0 {
+                    let line = self.lines.remove(self.row);
+                    self.row -= 1;
+                    self.col = char_len(&self.lines[self.row]);
+                    self.lines[self.row].push_str(&line);
+                }
+            }
+            KeyCode::Delete => self.delete_char(),
+            KeyCode::Left => self.col = self.col.saturating_sub(1),
+            KeyCode::Right => self.col = (self.col + 1).min(char_len(&self.lines[self.row])),
+            KeyCode::Up => self.move_rows(-1),
+            KeyCode::Down => self.move_rows(1),
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = char_len(&self.lines[self.row]),
+            _ => {}
+        }
+    }
+
+    fn normal_key(&mut self, key: KeyEvent) {
+        let KeyCode::Char(c) = key.code else {
+            self.pending = None;
+            match key.code {
+                KeyCode::Left => self.col = self.col.saturating_sub(1),
+                KeyCode::Right => self.col += 1,
+                KeyCode::Up => self.move_rows(-1),
+                KeyCode::Down => self.move_rows(1),
+                KeyCode::Home => self.col = 0,
+                KeyCode::End => self.col = usize::MAX,
+                _ => {}
+            }
+            self.clamp_col();
+            return;
+        };
+
+        match (self.pending.take(), c) {
+            (Some('d'), 'd') => {
+                self.checkpoint();
+                self.lines.remove(self.row);
+                if self.lines.is_empty() {
+                    self.lines.push(String::new());
+                }
+                self.row = self.row.min(self.lines.len() - 1);
+                self.col = 0;
+            }
+            (Some('g'), 'g') => {
+                self.row = 0;
+                self.col = 0;
+            }
+            (Some(_), _) => {}
+            (None, 'd' | 'g') => self.pending = Some(c),
+            (None, 'h') => self.col = self.col.saturating_sub(1),
+            (None, 'l') => self.col += 1,
+            (None, 'j') => self.move_rows(1),
+            (None, 'k') => self.move_rows(-1),
+            (None, '0') => self.col = 0,
+            (None, '$') => self.col = usize::MAX,
+            (None, 'w') => self.word_forward(),
+            (None, 'b') => self.word_back(),
+            (None, 'G') => self.row = self.lines.len() - 1,
+            (None, 'x') if !self.lines[self.row].is_empty() => {
+                self.checkpoint();
+                self.delete_char();
+            }
+            (None, 'D') => {
+                self.checkpoint();
+                let at = byte_index(&self.lines[self.row], self.col);
+                self.lines[self.row].truncate(at);
+            }
+            (None, 'u') => self.undo(),
+            (None, 'i') => self.start_insert(),
+            (None, 'a') => {
+                self.col = (self.col + 1).min(char_len(&self.lines[self.row]));
+                self.start_insert();
+            }
+            (None, 'I') => {
+                self.col = 0;
+                self.start_insert();
+            }
+            (None, 'A') => {
+                self.col = char_len(&self.lines[self.row]);
+                self.start_insert();
+            }
+            (None, 'o') | (None, 'O') => {
+                self.start_insert();
+                if c == 'o' {
+                    self.row += 1;
+                }
+                self.lines.insert(self.row, String::new());
+                self.col = 0;
+            }
+            _ => {}
+        }
+        if self.mode == EditorMode::Normal {
+            self.clamp_col();
+        }
+    }
+
+    fn start_insert(&mut self) {
+        self.checkpoint();
+        self.mode = EditorMode::Insert;
+    }
+
+    fn insert_str(&mut self, text: &str) {
+        let at = byte_index(&self.lines[self.row], self.col);
+        self.lines[self.row].insert_str(at, text);
+        self.col += text.chars().count();
+    }
+
+    fn delete_char(&mut self) {
+        let line = &mut self.lines[self.row];
+        if self.col < char_len(line) {
+            let at = byte_index(line, self.col);
+            line.remove(at);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    fn move_rows(&mut self, delta: isize) {
+        self.row = self
+            .row
+            .saturating_add_signed(delta)
+            .min(self.lines.len() - 1);
+        let len = char_len(&self.lines[self.row]);
+        self.col = self.col.min(len);
+    }
+
+    // In normal mode the cursor sits on a character, not after the last one
+    fn clamp_col(&mut self) {
+        let len = char_len(&self.lines[self.row]);
+        self.col = self.col.min(len.saturating_sub(1));
+    }
+
+    fn word_forward(&mut self) {
+        let chars: Vec<char> = self.lines[self.row].chars().collect();
+        let mut col = self.col;
+        if let Some(&c) = chars.get(col) {
+            let class = char_class(c);
+            while chars.get(col).is_some_and(|&c| char_class(c) == class) {
+                col += 1;
+            }
+        }
+        while chars.get(col).is_some_and(|c| c.is_whitespace()) {
+            col += 1;
+        }
+        if col >= chars.len() && self.row + 1 < self.lines.len() {
+            self.row += 1;
+            let line = &self.lines[self.row];
+            self.col = line.chars().take_while(|c| c.is_whitespace()).count();
+        } else {
+            self.col = col;
+        }
+    }
+
+    fn word_back(&mut self) {
+        if self.col == 0 {
+            if self.row > 0 {
+                self.row -= 1;
+                self.col = char_len(&self.lines[self.row]);
+            }
+            return;
+        }
+        let chars: Vec<char> = self.lines[self.row].chars().collect();
+        let mut col = self.col.min(chars.len());
+        while col > 0 && chars[col - 1].is_whitespace() {
+            col -= 1;
+        }
+        if col > 0 {
+            let class = char_class(chars[col - 1]);
+            while col > 0 && char_class(chars[col - 1]) == class {
+                col -= 1;
+            }
+        }
+        self.col = col;
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            lines: self.lines.clone(),
+            row: self.row,
+            col: self.col,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.lines = snapshot.lines;
+        self.row = snapshot.row;
+        self.col = snapshot.col;
+    }
+
+    // Record the state before an edit
+    fn checkpoint(&mut self) {
+        self.undo.push(self.snapshot());
+        self.redo.clear();
+    }
+
+    fn undo(&mut self) {
+        if let Some(snapshot) = self.undo.pop() {
+            self.redo.push(self.snapshot());
+            self.restore(snapshot);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(snapshot) = self.redo.pop() {
+            self.undo.push(self.snapshot());
+            self.restore(snapshot);
+        }
+        self.clamp_col();
+    }
+
+    /// Key hints for the status bar.
+    pub fn hint(&self) -> &'static str {
+        match self.mode {
+            EditorMode::Insert => "-- INSERT --  Esc normal mode | Ctrl-S save",
+            EditorMode::Normal => "i/a/o insert | u undo | Ctrl-R redo | Ctrl-S save | q close",
+        }
+    }
+}
+
+fn char_len(line: &str) -> usize {
+    line.chars().count()
+}
+
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices().nth(col).map_or(line.len(), |(i, _)| i)
+}
+
+fn char_class(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+/// Draw the pane with soft-wrapped lines and place the terminal cursor.
+pub fn render(f: &mut Frame, pane: &mut EditorPane, area: Rect) {
+    let modified = if pane.is_dirty() { " [+]" } else { "" };
+    let block = Block::default()
+        .title(Span::styled(
+            format!("Editing: {}{}", pane.id, modified),
+            Style::default().fg(PRIMARY_COLOR).add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Span::styled(
+            match pane.mode {
+                EditorMode::Normal => " NORMAL ",
+                EditorMode::Insert => " INSERT ",
+            },
+            Style::default().fg(HIGHLIGHT_COLOR),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_COLOR));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+
+    // Split every line into rows of `width` characters
+    let width = inner.width as usize;
+    let height = inner.height as usize;
+    let mut rows: Vec<String> = Vec::new();
+    let mut cursor = (0, 0);
+    for (i, line) in pane.lines.iter().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let start = rows.len();
+        if chars.is_empty() {
+            rows.push(String::new());
+        }
+        for chunk in chars.chunks(width) {
+            rows.push(chunk.iter().collect());
+        }
+        if i == pane.row {
+            cursor = (start + pane.col / width, pane.col % width);
+            // Cursor just past a full last row wraps onto a row of its own
+            if cursor.0 >= rows.len() {
+                rows.push(String::new());
+            }
+        }
+    }
+
+    if cursor.0 < pane.scroll {
+        pane.scroll = cursor.0;
+    } else if cursor.0 >= pane.scroll + height {
+        pane.scroll = cursor.0 + 1 - height;
+    }
+
+    let visible: Vec<Line> = rows
+        .into_iter()
+        .skip(pane.scroll)
+        .take(height)
+        .map(|row| Line::styled(row, Style::default().fg(text_color())))
+        .collect();
+    f.render_widget(Paragraph::new(visible), inner);
+
+    f.set_cursor_position(Position {
+        x: inner.x + cursor.1 as u16,
+        y: inner.y + (cursor.0 - pane.scroll) as u16,
+    });
+}
@@ -1,14 +1,18 @@
 // src/ui/entries_tab.rs
 use chrono::NaiveDate;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph, Wrap, Padding},
+    widgets::{
+        Block, BorderType, Borders, List, ListItem, Padding, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Wrap,
+    },
     Frame,
 };
-use crate::app::App;
-use crate::ui::util::{PRIMARY_COLOR, BORDER_COLOR, HIGHLIGHT_COLOR, TEXT_COLOR, SUBTLE_TEXT, themed_block};
+use crate::{app::{App, Focus}, config::settings::config, journal::{entry::JournalEntry, search::Query}};
+use crate::ui::{editor_pane, markdown};
+use crate::ui::util::{PRIMARY_COLOR, BORDER_COLOR, HIGHLIGHT_COLOR, text_color, SUBTLE_TEXT};
 
 pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
     let today = chrono::Local::now().format("%Y-%m-%d").to_string();
@@ -24,38 +28,50 @@ pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
         .items
         .iter()
         .map(|e| {
-            let mut style = Style::default().fg(TEXT_COLOR);
+            let mut style = Style::default().fg(text_color());
             if e.date == today {
                 style = Style::default().fg(HIGHLIGHT_COLOR).add_modifier(Modifier::BOLD);
             }
             
             // Use formatted date display for entries list
-            let date_display = if let Ok(parsed_date) = NaiveDate::parse_from_str(&e.date, "%Y-%m-%d") {
-                parsed_date.format("%b %d, %Y").to_string()
+            let mut date_display = if let Ok(parsed_date) = NaiveDate::parse_from_str(&e.date, "%Y-%m-%d") {
+                parsed_date.format(&config().date_format).to_string()
             } else {
                 e.date.clone()
             };
+            if let Some(time) = e.time_label() {
+                date_display = format!("{date_display} {time}");
+            }
             
-            ListItem::new(Line::from(vec![
-                Span::styled(date_display, style),
-                Span::styled(
-                    format!(" ({} bytes)", e.size),
-                    Style::default().fg(SUBTLE_TEXT),
-                ),
-            ]))
+            let mut spans = vec![Span::styled(date_display, style)];
+            if let Some(title) = &e.meta.title {
+                spans.push(Span::styled(format!(" {}", title), Style::default().fg(text_color())));
+            }
+            spans.push(Span::styled(
+                format!(" ({} bytes)", e.size),
+                Style::default().fg(SUBTLE_TEXT),
+            ));
+            ListItem::new(Line::from(spans))
         })
         .collect();
     
+    let mut list_title = match &app.search {
+        Some(query) => format!("Journal Entries /{}", query.input),
+        None => "Journal Entries".to_string(),
+    };
+    if let Some(tag) = &app.tag_filter {
+        list_title.push_str(&format!(" #{tag}"));
+    }
     let entries_list = List::new(items)
         .block(
             Block::default()
                 .title(Span::styled(
-                    "Journal Entries",
+                    list_title,
                     Style::default().fg(PRIMARY_COLOR).add_modifier(Modifier::BOLD)
                 ))
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(BORDER_COLOR))
+                .border_style(Style::default().fg(focus_color(app, Focus::List)))
         )
         .highlight_style(
             Style::default()
@@ -66,33 +82,161 @@ pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
     
     f.render_stateful_widget(entries_list, chunks[0], &mut app.entries_list.state);
     
+    if let Some(pane) = app.editor_pane.as_mut() {
+        editor_pane::render(f, pane, chunks[1]);
+        return;
+    }
+
     // Preview content
-    let selected = app.entries_list.state.selected();
+    let selected = app.entries_list.selected_item();
+    let mut preview_title = selected
+        .and_then(|entry| entry.title())
+        .map(|title| format!("Preview: {}", title))
+        .unwrap_or_else(|| "Preview".to_string());
+    if app.raw_preview {
+        preview_title.push_str(" (raw)");
+    }
     let content = match selected {
-        Some(i) if !app.entries_list.items.is_empty() => {
-            let entry = &app.entries_list.items[i];
-            
-            Text::from(entry.content.clone())
+        Some(entry) => {
+            let mut text = meta_header(entry);
+            if app.raw_preview {
+                match &app.search {
+                    Some(query) => text.extend(highlight_matches(entry.body(), query)),
+                    None => text.extend(Text::from(entry.body().to_string())),
+                }
+            } else {
+                // Borders and padding take four columns
+                let rendered = markdown::render(entry.body(), chunks[1].width.saturating_sub(4));
+                match &app.search {
+                    Some(query) => text.extend(highlight_spans(rendered, query)),
+                    None => text.extend(rendered),
+                }
+            }
+            text
         }
-        _ => Text::from(Span::styled(
+        None => Text::from(Span::styled(
             "Select an entry to view its content",
             Style::default().fg(SUBTLE_TEXT).add_modifier(Modifier::ITALIC)
         )),
     };
     
-    let preview = Paragraph::new(content)
-        .block(
-            Block::default()
-                .title(Span::styled(
-                    "Preview",
-                    Style::default().fg(PRIMARY_COLOR).add_modifier(Modifier::BOLD)
-                ))
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(BORDER_COLOR))
-                .padding(Padding::new(1, 1, 0, 0)),
-        )
-        .wrap(Wrap { trim: false });
-    
-    f.render_widget(preview, chunks[1]);
+    // Clamp the remembered offset now that the wrapped height is known
+    let preview = Paragraph::new(content).wrap(Wrap { trim: false });
+    let height = chunks[1].height.saturating_sub(2);
+    let total = preview.line_count(chunks[1].width.saturating_sub(4)) as u16;
+    let max_scroll = total.saturating_sub(height);
+    let scroll = app.preview_scroll().min(max_scroll);
+    app.preview_height = height;
+    app.set_preview_scroll(scroll);
+
+    let mut block = Block::default()
+        .title(Span::styled(
+            preview_title,
+            Style::default().fg(PRIMARY_COLOR).add_modifier(Modifier::BOLD)
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(focus_color(app, Focus::Preview)))
+        .padding(Padding::new(1, 1, 0, 0));
+    if max_scroll > 0 {
+        let bottom = (scroll + height).min(total);
+        block = block.title_bottom(
+            Line::styled(
+                format!(" {}-{}/{} ", scroll + 1, bottom, total),
+                Style::default().fg(SUBTLE_TEXT),
+            )
+            .right_aligned(),
+        );
+    }
+    f.render_widget(preview.block(block).scroll((scroll, 0)), chunks[1]);
+
+    if max_scroll > 0 {
+        let mut state = ScrollbarState::new(max_scroll as usize).position(scroll as usize);
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(Style::default().fg(BORDER_COLOR)),
+            chunks[1].inner(Margin { vertical: 1, horizontal: 0 }),
+            &mut state,
+        );
+    }
+}
+
+// The focused pane gets a highlighted border
+fn focus_color(app: &App, pane: Focus) -> Color {
+    if app.focus == pane {
+        HIGHLIGHT_COLOR
+    } else {
+        BORDER_COLOR
+    }
+}
+
+// Tags, category and mood from the front matter, shown above the body
+fn meta_header(entry: &JournalEntry) -> Text<'static> {
+    let meta = &entry.meta;
+    let mut spans = Vec::new();
+    for tag in &meta.tags {
+        spans.push(Span::styled(format!("#{} ", tag), Style::default().fg(HIGHLIGHT_COLOR)));
+    }
+    if let Some(category) = &meta.category {
+        spans.push(Span::styled(format!("[{}] ", category), Style::default().fg(PRIMARY_COLOR)));
+    }
+    if let Some(mood) = &meta.mood {
+        spans.push(Span::styled(format!("mood: {}", mood), Style::default().fg(SUBTLE_TEXT)));
+    }
+
+    if spans.is_empty() {
+        Text::default()
+    } else {
+        Text::from(vec![Line::from(spans), Line::from("")])
+    }
+}
+
+fn highlight_matches(body: &str, query: &Query) -> Text<'static> {
+    let hit = Style::default().fg(Color::Black).bg(HIGHLIGHT_COLOR);
+    let lines: Vec<Line> = body
+        .lines()
+        .map(|line| {
+            let mut spans = Vec::new();
+            let mut last = 0;
+            for (start, end) in query.find_ranges(line) {
+                spans.push(Span::raw(line[last..start].to_string()));
+                spans.push(Span::styled(line[start..end].to_string(), hit));
+                last = end;
+            }
+            spans.push(Span::raw(line[last..].to_string()));
+            Line::from(spans)
+        })
+        .collect();
+    Text::from(lines)
+}
+
+// Same as `highlight_matches` but for already styled text; a match that
+// crosses a style boundary isn't highlighted.
+fn highlight_spans(text: Text<'static>, query: &Query) -> Text<'static> {
+    let lines: Vec<Line> = text
+        .lines
+        .into_iter()
+        .map(|line| {
+            let mut spans = Vec::new();
+            for span in line.spans {
+                let ranges = query.find_ranges(&span.content);
+                if ranges.is_empty() {
+                    spans.push(span);
+                    continue;
+                }
+                let hit = span.style.fg(Color::Black).bg(HIGHLIGHT_COLOR);
+                let mut last = 0;
+                for (start, end) in ranges {
+                    spans.push(Span::styled(span.content[last..start].to_string(), span.style));
+                    spans.push(Span::styled(span.content[start..end].to_string(), hit));
+                    last = end;
+                }
+                spans.push(Span::styled(span.content[last..].to_string(), span.style));
+            }
+            Line::from(spans).style(line.style)
+        })
+        .collect();
+    Text::from(lines)
 }
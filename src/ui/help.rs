@@ -2,15 +2,19 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
-use crate::ui::util::{
-    centered_rect, PRIMARY_COLOR, BORDER_COLOR, ACCENT_COLOR, HIGHLIGHT_COLOR, TEXT_COLOR,
+use crate::{
+    config::settings::Keybindings,
+    ui::util::{centered_rect, PRIMARY_COLOR, BORDER_COLOR, ACCENT_COLOR, text_color},
 };
 
-pub fn render(f: &mut Frame, area: Rect) {
+pub fn render(f: &mut Frame, keys: &Keybindings, area: Rect) {
+    let area = centered_rect(60, 70, area);
+    f.render_widget(Clear, area);
+
     let help_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
@@ -27,15 +31,31 @@ pub fn render(f: &mut Frame, area: Rect) {
     f.render_widget(title_block, help_layout[0]);
 
     // Render the rest of the help content here
+    let [quit, help, new, delete, search, edit] =
+        [keys.quit, keys.help, keys.new, keys.delete, keys.search, keys.edit].map(|c| c.to_string());
     let help_lines = vec![
-        create_key_line("q", "", "Quit the application"),
-        create_key_line("←/→", "", "Navigate between tabs"),
-        create_key_line("Enter", "", "Open selected entry"),
+        create_key_line(&quit, "", "Quit the application"),
+        create_key_line("Tab", "⇧Tab", "Cycle list, preview and tabs"),
+        create_key_line("←/→", "", "Focus the entry list/preview"),
+        create_key_line("PgUp/PgDn", "", "Scroll the preview (j/k, g/G when focused)"),
+        create_key_line("Enter", "", "Open selected entry in your editor"),
         create_key_line("Esc", "", "Close modals or go back"),
-        create_key_line("n", "", "Create a new entry"),
-        create_key_line("d", "", "Delete selected entry"),
-        create_key_line("e", "", "Edit selected entry"),
+        create_key_line(&new, "", "Create a new entry (pick a template if any)"),
+        create_key_line("N", "", "Open or create the entry for a date"),
+        create_key_line(&delete, "", "Move selected entry to the trash"),
+        create_key_line("u", "", "Undo the last delete"),
+        create_key_line("H", "", "Revision history of selected entry"),
+        create_key_line(&edit, "", "Edit selected entry here (Ctrl-S saves, q closes)"),
         create_key_line("j/k", "", "Move down/up in list"),
+        create_key_line("r", "", "Toggle rendered/raw Markdown preview"),
+        create_key_line(&help, "", "Toggle this help"),
+        create_key_line(&search, "", "Search entries (\"phrase\", /regex/)"),
+        create_key_line("J", "", "Switch to the next journal"),
+        create_key_line("Enter", "", "On the Tags tab: list entries with that tag (Esc clears)"),
+        create_key_line("S", "", "Sync the journal with its git remote"),
+        create_key_line("h/j/k/l", "", "Move day cursor (Calendar)"),
+        create_key_line("[/]", "", "Previous/next month (Calendar)"),
+        create_key_line("t", "", "Jump to today (Calendar)"),
     ];
 
     let help_paragraph = Paragraph::new(help_lines)
@@ -45,7 +65,7 @@ pub fn render(f: &mut Frame, area: Rect) {
                 .title("Shortcuts")
                 .border_style(Style::default().fg(BORDER_COLOR)),
         )
-        .style(Style::default().fg(TEXT_COLOR));
+        .style(Style::default().fg(text_color()));
 
     f.render_widget(help_paragraph, help_layout[1]);
 }
@@ -73,7 +93,7 @@ pub fn create_key_line<'a>(key: &'a str, alt_key: &'a str, description: &'a str)
     }
 
     spans.push(Span::raw(" "));
-    spans.push(Span::styled(description, Style::default().fg(TEXT_COLOR)));
+    spans.push(Span::styled(description, Style::default().fg(text_color())));
 
     Line::from(spans)
 }
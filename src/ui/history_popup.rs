@@ -0,0 +1,149 @@
+// src/ui/history_popup.rs
+//
+// Revisions of the selected entry on the left, a diff of the highlighted
+// revision against the current file on the right.
+use std::{error::Error, path::PathBuf};
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::{
+    journal::{
+        entry::JournalEntry,
+        history::{self, DiffKind, DiffLine, Revision},
+        storage::read_entry,
+    },
+    ui::util::{
+        centered_rect, text_color, ACCENT_COLOR, BORDER_COLOR, HIGHLIGHT_COLOR, PRIMARY_COLOR,
+        SUBTLE_TEXT,
+    },
+};
+
+pub struct HistoryView {
+    pub id: String,
+    pub path: PathBuf,
+    pub revisions: Vec<Revision>,
+    pub state: ListState,
+    diff: Vec<DiffLine>,
+    scroll: u16,
+}
+
+impl HistoryView {
+    pub fn open(entry: &JournalEntry) -> Result<HistoryView, Box<dyn Error>> {
+        let mut view = HistoryView {
+            id: entry.id.clone(),
+            path: entry.path.clone(),
+            revisions: history::revisions(&entry.id)?,
+            state: ListState::default(),
+            diff: Vec::new(),
+            scroll: 0,
+        };
+        if !view.revisions.is_empty() {
+            view.select(0);
+        }
+        Ok(view)
+    }
+
+    pub fn selected(&self) -> Option<&Revision> {
+        self.state.selected().and_then(|i| self.revisions.get(i))
+    }
+
+    pub fn select(&mut self, index: usize) {
+        let Some(revision) = self.revisions.get(index) else {
+            return;
+        };
+        let current = read_entry(&self.path).unwrap_or_default();
+        self.diff = history::diff_lines(&revision.content().unwrap_or_default(), &current);
+        self.scroll = 0;
+        self.state.select(Some(index));
+    }
+
+    pub fn next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.select((i + 1).min(self.revisions.len().saturating_sub(1)));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.select(i.saturating_sub(1));
+        }
+    }
+
+    pub fn scroll_diff(&mut self, delta: i16) {
+        self.scroll = self.scroll.saturating_add_signed(delta);
+    }
+}
+
+pub fn render(f: &mut Frame, view: &mut HistoryView, area: Rect) {
+    let area = centered_rect(80, 80, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" History: {} ", view.id),
+            Style::default().fg(PRIMARY_COLOR).add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Span::styled(
+            " j/k select | J/K scroll diff | Enter restore | Esc close ",
+            Style::default().fg(SUBTLE_TEXT),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_COLOR));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if view.revisions.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled("No revisions recorded yet", Style::default().fg(SUBTLE_TEXT))),
+            inner,
+        );
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(24), Constraint::Min(0)])
+        .split(inner);
+
+    let items: Vec<ListItem> = view
+        .revisions
+        .iter()
+        .map(|revision| {
+            ListItem::new(Span::styled(
+                revision.saved_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                Style::default().fg(text_color()),
+            ))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::RIGHT).border_style(Style::default().fg(BORDER_COLOR)))
+        .highlight_style(
+            Style::default()
+                .bg(HIGHLIGHT_COLOR)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_stateful_widget(list, chunks[0], &mut view.state);
+
+    let lines: Vec<Line> = if view.diff.is_empty() {
+        vec![Line::styled(" Same as the current entry", Style::default().fg(SUBTLE_TEXT))]
+    } else {
+        view.diff
+            .iter()
+            .map(|line| match line.kind {
+                DiffKind::Context => Line::styled(format!("  {}", line.text), Style::default().fg(text_color())),
+                DiffKind::Added => Line::styled(format!("+ {}", line.text), Style::default().fg(PRIMARY_COLOR)),
+                DiffKind::Removed => Line::styled(format!("- {}", line.text), Style::default().fg(ACCENT_COLOR)),
+                DiffKind::Separator => Line::styled(format!("  {}", line.text), Style::default().fg(SUBTLE_TEXT)),
+            })
+            .collect()
+    };
+    f.render_widget(Paragraph::new(lines).scroll((view.scroll, 0)), chunks[1]);
+}
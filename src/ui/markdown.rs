@@ -0,0 +1,228 @@
+// src/ui/markdown.rs
+//
+// Turns an entry body into styled ratatui text for the preview pane.
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+};
+
+use crate::ui::util::{
+    text_color, ACCENT_COLOR, BORDER_COLOR, HIGHLIGHT_COLOR, PRIMARY_COLOR, SECONDARY_TEXT,
+    SUBTLE_TEXT,
+};
+
+/// Markdown parser with the extensions entries may use. Shared with the
+/// HTML export so both read entries the same way.
+pub fn parser(body: &str) -> Parser<'_> {
+    Parser::new_ext(body, Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH)
+}
+
+/// Render `body` as Markdown. `width` is only used for horizontal rules.
+pub fn render(body: &str, width: u16) -> Text<'static> {
+    let mut renderer = Renderer {
+        width: width.max(1) as usize,
+        ..Default::default()
+    };
+    for event in parser(body) {
+        renderer.event(event);
+    }
+    renderer.flush();
+    // Drop the blank line left after the last block
+    while renderer.lines.last().is_some_and(|l| l.spans.is_empty()) {
+        renderer.lines.pop();
+    }
+    Text::from(renderer.lines)
+}
+
+#[derive(Default)]
+struct Renderer {
+    width: usize,
+    lines: Vec<Line<'static>>,
+    /// Spans of the line being built
+    spans: Vec<Span<'static>>,
+    /// Inline styles in effect, innermost last
+    styles: Vec<Style>,
+    quote_depth: usize,
+    /// Next number for each open list, `None` for bullet lists
+    lists: Vec<Option<u64>>,
+    code_block: bool,
+    link: Option<String>,
+}
+
+impl Renderer {
+    fn style(&self) -> Style {
+        self.styles
+            .iter()
+            .fold(Style::default().fg(text_color()), |acc, s| acc.patch(*s))
+    }
+
+    fn push(&mut self, text: impl Into<String>, style: Style) {
+        if self.spans.is_empty() && self.quote_depth > 0 {
+            self.spans.push(Span::styled(
+                "│ ".repeat(self.quote_depth),
+                Style::default().fg(SUBTLE_TEXT),
+            ));
+        }
+        self.spans.push(Span::styled(text.into(), style));
+    }
+
+    fn flush(&mut self) {
+        if !self.spans.is_empty() {
+            self.lines.push(Line::from(std::mem::take(&mut self.spans)));
+        }
+    }
+
+    // Blank line between blocks, but not inside lists
+    fn end_block(&mut self) {
+        self.flush();
+        if self.lists.is_empty() && self.lines.last().is_some_and(|l| !l.spans.is_empty()) {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) if self.code_block => {
+                let style = Style::default().fg(SECONDARY_TEXT);
+                for line in text.lines() {
+                    self.push("  ", style);
+                    self.push(line.to_string(), style);
+                    self.flush();
+                }
+            }
+            Event::Text(text) => {
+                let style = self.style();
+                self.push(text.to_string(), style);
+            }
+            Event::Code(code) => self.push(code.to_string(), Style::default().fg(ACCENT_COLOR)),
+            Event::SoftBreak => {
+                let style = self.style();
+                self.push(" ", style);
+            }
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.flush();
+                self.push("─".repeat(self.width), Style::default().fg(BORDER_COLOR));
+                self.end_block();
+            }
+            Event::TaskListMarker(checked) => {
+                // Replace the bullet pushed by the list item
+                self.spans.pop();
+                let (marker, color) = if checked {
+                    ("☑ ", PRIMARY_COLOR)
+                } else {
+                    ("☐ ", HIGHLIGHT_COLOR)
+                };
+                self.push(marker, Style::default().fg(color));
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                self.push(html.trim_end().to_string(), Style::default().fg(SUBTLE_TEXT));
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                let style = match level {
+                    HeadingLevel::H1 => Style::default()
+                        .fg(PRIMARY_COLOR)
+                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    HeadingLevel::H2 => Style::default().fg(PRIMARY_COLOR).add_modifier(Modifier::BOLD),
+                    _ => Style::default().fg(HIGHLIGHT_COLOR).add_modifier(Modifier::BOLD),
+                };
+                self.styles.push(style);
+            }
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth += 1;
+                self.styles.push(Style::default().fg(SECONDARY_TEXT).add_modifier(Modifier::ITALIC));
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush();
+                self.code_block = true;
+                if let CodeBlockKind::Fenced(lang) = kind
+                    && !lang.is_empty()
+                {
+                    self.push(format!("  {}", lang), Style::default().fg(SUBTLE_TEXT));
+                    self.flush();
+                }
+            }
+            Tag::List(start) => {
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}. ", *n - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.push(indent, Style::default());
+                self.push(marker, Style::default().fg(HIGHLIGHT_COLOR));
+            }
+            Tag::Emphasis => self.styles.push(Style::default().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.styles.push(Style::default().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self.styles.push(Style::default().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } => {
+                self.link = Some(dest_url.to_string());
+                self.styles.push(Style::default().fg(BORDER_COLOR).add_modifier(Modifier::UNDERLINED));
+            }
+            Tag::Image { dest_url, .. } => {
+                self.link = Some(dest_url.to_string());
+                self.styles.push(Style::default().fg(SUBTLE_TEXT));
+                self.push("🖼 ", Style::default().fg(SUBTLE_TEXT));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.end_block(),
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.end_block();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.styles.pop();
+                self.flush();
+                self.quote_depth -= 1;
+                if self.quote_depth == 0 {
+                    self.end_block();
+                }
+            }
+            TagEnd::CodeBlock => {
+                self.code_block = false;
+                self.end_block();
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                self.end_block();
+            }
+            TagEnd::Item => self.flush(),
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link | TagEnd::Image => {
+                self.styles.pop();
+                if let Some(url) = self.link.take() {
+                    let shown = self.spans.last().is_some_and(|s| s.content == url);
+                    if !shown {
+                        self.push(format!(" ({})", url), Style::default().fg(SUBTLE_TEXT));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
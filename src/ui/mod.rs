@@ -1,7 +1,14 @@
 // src/ui/mod.rs
 pub mod calendar_tab;
+pub mod editor_pane;
 pub mod entries_tab;
 pub mod help;
+pub mod history_popup;
+pub mod markdown;
+pub mod popup;
 pub mod settings_tab;
+pub mod tags_tab;
+pub mod template_picker;
+#[allow(clippy::module_inception)]
 pub mod ui;
 pub mod util;
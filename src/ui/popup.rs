@@ -0,0 +1,79 @@
+// src/ui/popup.rs
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::ui::util::{text_color, ACCENT_COLOR, HIGHLIGHT_COLOR, SUBTLE_TEXT};
+
+/// Small centered yes/no dialog.
+pub fn render_confirm(f: &mut Frame, title: &str, message: &str, area: Rect) {
+    let width = (message.chars().count() as u16 + 6).clamp(30, area.width);
+    let height = 5.min(area.height);
+    let area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    f.render_widget(Clear, area);
+
+    let lines = vec![
+        Line::styled(message.to_string(), Style::default().fg(text_color())),
+        Line::default(),
+        Line::from(vec![
+            Span::styled(" y ", Style::default().fg(HIGHLIGHT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::styled("confirm   ", Style::default().fg(SUBTLE_TEXT)),
+            Span::styled(" any other key ", Style::default().fg(HIGHLIGHT_COLOR).add_modifier(Modifier::BOLD)),
+            Span::styled("cancel", Style::default().fg(SUBTLE_TEXT)),
+        ]),
+    ];
+    let popup = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", title),
+                    Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(ACCENT_COLOR)),
+        );
+    f.render_widget(popup, area);
+}
+
+/// Small centered one-line text prompt with a hint line under the input.
+pub fn render_input(f: &mut Frame, title: &str, input: &str, hint: &str, area: Rect) {
+    let width = 56.min(area.width);
+    let height = 5.min(area.height);
+    let area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    f.render_widget(Clear, area);
+
+    let lines = vec![
+        Line::styled(format!("{}█", input), Style::default().fg(text_color())),
+        Line::default(),
+        Line::styled(hint.to_string(), Style::default().fg(SUBTLE_TEXT)),
+    ];
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(Span::styled(
+                format!(" {} ", title),
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD),
+            ))
+            .title_bottom(Span::styled(" Enter open | Esc cancel ", Style::default().fg(SUBTLE_TEXT)))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ACCENT_COLOR)),
+    );
+    f.render_widget(popup, area);
+}
@@ -7,9 +7,10 @@ use ratatui::{
     Frame,
 };
 
-use crate::{app::App, config::settings::get_journal_dir};
+use crate::{app::App, config::settings::{active_journal, config, get_journal_dir}};
 
 pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
+    let config = config();
     let settings_text = Text::from(vec![
         Line::from(vec![
             Span::styled(
@@ -23,8 +24,22 @@ pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
             Span::styled(&app.editor, Style::default()),
         ]),
         Line::from(""),
+        setting_line("Journal: ", &active_journal()),
         Line::from("Journal entries are stored in:"),
         Line::from(format!("{}", get_journal_dir().display())),
+        Line::from(""),
+        setting_line("Date format: ", &config.date_format),
+        setting_line("Theme: ", &format!("{:?}", config.theme).to_lowercase()),
+        setting_line("Week starts on: ", &format!("{:?}", config.week_start)),
+        setting_line(
+            "Default template: ",
+            config.default_template.as_deref().unwrap_or("(built-in)"),
+        ),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Change settings with `notes config <key> <value>`",
+            Style::default().fg(Color::DarkGray),
+        )),
     ]);
 
     let settings = Paragraph::new(settings_text)
@@ -37,3 +52,10 @@ pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
 
     f.render_widget(settings, area);
 }
+
+fn setting_line<'a>(label: &'a str, value: &str) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(label, Style::default().fg(Color::Yellow)),
+        Span::raw(value.to_string()),
+    ])
+}
@@ -0,0 +1,89 @@
+// src/ui/tags_tab.rs
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    app::App,
+    config::settings::config,
+    ui::util::{themed_block, ACCENT_COLOR, HIGHLIGHT_COLOR, PRIMARY_COLOR, SUBTLE_TEXT, text_color},
+};
+
+pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
+    if app.tags.items.is_empty() {
+        let hint = Paragraph::new(vec![
+            Line::styled("No tags yet.", Style::default().fg(text_color())),
+            Line::default(),
+            Line::styled(
+                "Add `tags: [work, ideas]` to an entry's front matter or write #tags in the text.",
+                Style::default().fg(SUBTLE_TEXT),
+            ),
+        ])
+        .wrap(Wrap { trim: true })
+        .block(themed_block("Tags"));
+        f.render_widget(hint, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(area);
+
+    let width = app.tags.items.iter().map(|(tag, _)| tag.chars().count()).max().unwrap_or(0);
+    let items: Vec<ListItem> = app
+        .tags
+        .items
+        .iter()
+        .map(|(tag, count)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("#{tag:<width$} "), Style::default().fg(text_color())),
+                Span::styled(count.to_string(), Style::default().fg(SUBTLE_TEXT)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(themed_block("Tags"))
+        .highlight_style(
+            Style::default()
+                .bg(HIGHLIGHT_COLOR)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_stateful_widget(list, chunks[0], &mut app.tags.state);
+
+    // Entries carrying the highlighted tag, newest first
+    let Some((tag, _)) = app.tags.selected_item() else {
+        return;
+    };
+    let date_format = config().date_format.clone();
+    let mut lines: Vec<Line> = app
+        .entries
+        .iter()
+        .filter(|entry| entry.has_tag(tag))
+        .map(|entry| {
+            let mut date = entry.timestamp.date().format(&date_format).to_string();
+            if let Some(time) = entry.time_label() {
+                date = format!("{date} {time}");
+            }
+            Line::from(vec![
+                Span::styled(date, Style::default().fg(PRIMARY_COLOR)),
+                Span::styled(format!(" {}", entry.title().unwrap_or_default()), Style::default().fg(text_color())),
+            ])
+        })
+        .collect();
+    lines.push(Line::default());
+    lines.push(Line::from(vec![
+        Span::styled("j/k", Style::default().fg(ACCENT_COLOR)),
+        Span::styled(" move  ", Style::default().fg(SUBTLE_TEXT)),
+        Span::styled("Enter", Style::default().fg(ACCENT_COLOR)),
+        Span::styled(" show in Entries (Esc there clears)", Style::default().fg(SUBTLE_TEXT)),
+    ]));
+    let title = format!("Entries tagged #{tag}");
+    let entries = Paragraph::new(lines).block(themed_block(&title));
+    f.render_widget(entries, chunks[1]);
+}
@@ -0,0 +1,110 @@
+// src/ui/template_picker.rs
+//
+// Popup shown by `n` when templates exist: the journal's default first, then
+// every template in the templates folder.
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::{
+    config::settings::get_template_name,
+    journal::template::template_names,
+    ui::util::{text_color, BORDER_COLOR, HIGHLIGHT_COLOR, PRIMARY_COLOR, SUBTLE_TEXT},
+};
+
+pub struct TemplatePicker {
+    /// `None` is the journal's default template
+    pub choices: Vec<Option<String>>,
+    pub state: ListState,
+}
+
+impl TemplatePicker {
+    /// A picker over the user's templates, or `None` if there are none to pick.
+    pub fn open() -> Option<TemplatePicker> {
+        let names = template_names();
+        if names.is_empty() {
+            return None;
+        }
+        let mut choices = vec![None];
+        choices.extend(names.into_iter().map(Some));
+        let mut state = ListState::default();
+        state.select(Some(0));
+        Some(TemplatePicker { choices, state })
+    }
+
+    pub fn selected(&self) -> Option<&str> {
+        self.state
+            .selected()
+            .and_then(|i| self.choices.get(i))
+            .and_then(|choice| choice.as_deref())
+    }
+
+    pub fn next(&mut self) {
+        let i = self.state.selected().unwrap_or(0);
+        self.state.select(Some((i + 1) % self.choices.len()));
+    }
+
+    pub fn previous(&mut self) {
+        let i = self.state.selected().unwrap_or(0);
+        self.state.select(Some((i + self.choices.len() - 1) % self.choices.len()));
+    }
+}
+
+pub fn render(f: &mut Frame, picker: &mut TemplatePicker, area: Rect) {
+    let default_label = match get_template_name() {
+        Some(name) => format!("Default ({name})"),
+        None => "Default (built-in)".to_string(),
+    };
+    let items: Vec<ListItem> = picker
+        .choices
+        .iter()
+        .map(|choice| {
+            ListItem::new(Span::styled(
+                choice.clone().unwrap_or_else(|| default_label.clone()),
+                Style::default().fg(text_color()),
+            ))
+        })
+        .collect();
+
+    let width = picker
+        .choices
+        .iter()
+        .map(|c| c.as_deref().map_or(default_label.chars().count(), |n| n.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .max(28) as u16
+        + 6;
+    let width = width.min(area.width);
+    let height = (picker.choices.len() as u16 + 2).min(area.height);
+    let area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    f.render_widget(Clear, area);
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    " New entry from template ",
+                    Style::default().fg(PRIMARY_COLOR).add_modifier(Modifier::BOLD),
+                ))
+                .title_bottom(Span::styled(" Enter create | Esc cancel ", Style::default().fg(SUBTLE_TEXT)))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(BORDER_COLOR)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(HIGHLIGHT_COLOR)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_stateful_widget(list, area, &mut picker.state);
+}
@@ -1,15 +1,18 @@
 // src/ui/ui.rs
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Style, Modifier},
+    style::{Style, Modifier},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Tabs},
     Frame,
 };
+use chrono::Local;
 use crate::{
-    app::App,
-    ui::{calendar_tab, entries_tab, help, settings_tab},
-    ui::util::{PRIMARY_COLOR, BORDER_COLOR, BACKGROUND_COLOR, HIGHLIGHT_COLOR, TEXT_COLOR, SUBTLE_TEXT},
+    app::{App, InputMode},
+    config::settings::{active_journal, journal_names},
+    journal::dates::{parse_date, DateError},
+    ui::{calendar_tab, entries_tab, help, history_popup, popup, settings_tab, tags_tab, template_picker},
+    ui::util::{PRIMARY_COLOR, BORDER_COLOR, background_color, HIGHLIGHT_COLOR, text_color, SUBTLE_TEXT},
 };
 
 pub fn ui(f: &mut Frame, app: &mut App) {
@@ -24,7 +27,7 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         .split(f.area());
 
     // Render tabs
-    let titles: Vec<Line> = ["Entries", "Calendar", "Settings"]
+    let titles: Vec<Line> = ["Entries", "Calendar", "Tags", "Settings"]
         .iter()
         .map(|t| {
             let (first, rest) = t.split_at(1);
@@ -35,7 +38,7 @@ pub fn ui(f: &mut Frame, app: &mut App) {
                         .fg(HIGHLIGHT_COLOR)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(rest, Style::default().fg(TEXT_COLOR)),
+                Span::styled(rest, Style::default().fg(text_color())),
             ])
         })
         .collect();
@@ -47,43 +50,118 @@ pub fn ui(f: &mut Frame, app: &mut App) {
                 .border_style(Style::default().fg(BORDER_COLOR))
         )
         .select(app.tab_index)
-        .style(Style::default().fg(TEXT_COLOR))
+        .style(Style::default().fg(text_color()))
         .highlight_style(
             Style::default()
                 .fg(PRIMARY_COLOR)
                 .add_modifier(Modifier::BOLD),
         );
 
-    f.render_widget(tabs, chunks[0]);
+    // Journal switcher on the right of the tab bar
+    let journals = journal_names();
+    let active = active_journal();
+    let mut switcher = vec![Span::styled("📓 ", Style::default().fg(SUBTLE_TEXT))];
+    for (i, name) in journals.iter().enumerate() {
+        if i > 0 {
+            switcher.push(Span::styled(" │ ", Style::default().fg(SUBTLE_TEXT)));
+        }
+        let style = if *name == active {
+            Style::default().fg(PRIMARY_COLOR).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(SUBTLE_TEXT)
+        };
+        switcher.push(Span::styled(name.clone(), style));
+    }
+    if journals.len() > 1 {
+        switcher.push(Span::styled("  J", Style::default().fg(HIGHLIGHT_COLOR)));
+    }
+    let switcher = Line::from(switcher);
+
+    let header = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(switcher.width() as u16 + 1),
+        ])
+        .split(chunks[0]);
+
+    f.render_widget(tabs, header[0]);
+    f.render_widget(
+        Paragraph::new(switcher)
+            .alignment(Alignment::Right)
+            .block(
+                Block::default()
+                    .borders(Borders::BOTTOM)
+                    .border_style(Style::default().fg(BORDER_COLOR)),
+            ),
+        header[1],
+    );
 
     match app.tab_index {
         0 => entries_tab::render(f, app, chunks[1]),
         1 => calendar_tab::render(f, app, chunks[1]),
-        2 => settings_tab::render(f, app, chunks[1]),
+        2 => tags_tab::render(f, app, chunks[1]),
+        3 => settings_tab::render(f, app, chunks[1]),
         _ => unreachable!(),
     }
 
     // Render status bar
     let status = Line::from(vec![
         Span::raw(" "),
-        if !app.status_message.is_empty() {
+        if let InputMode::Editing = app.input_mode {
+            Span::styled(
+                format!("/{}█  {} matches", app.search_input, app.entries_list.items.len()),
+                Style::default().fg(HIGHLIGHT_COLOR),
+            )
+        } else if !app.status_message.is_empty() {
             Span::styled(&app.status_message, Style::default().fg(PRIMARY_COLOR))
+        } else if let Some(pane) = &app.editor_pane {
+            Span::styled(pane.hint(), Style::default().fg(SUBTLE_TEXT))
         } else {
             Span::styled(
-                format!("Press 'h' for help | {} entries", app.entries_list.items.len()),
+                format!("Press '{}' for help | {} entries", app.keys.help, app.entries_list.items.len()),
                 Style::default().fg(SUBTLE_TEXT),
             )
         },
     ]);
 
     let status_bar = Paragraph::new(status)
-        .style(Style::default().bg(BACKGROUND_COLOR))
+        .style(Style::default().bg(background_color()))
         .alignment(Alignment::Left);
 
     f.render_widget(status_bar, chunks[2]);
 
     // Render help overlay if requested
     if app.show_help {
-        help::render(f, f.size());
+        help::render(f, &app.keys, f.area());
+    }
+
+    if let Some(view) = app.history.as_mut() {
+        history_popup::render(f, view, f.area());
+    }
+
+    if let Some(picker) = app.template_picker.as_mut() {
+        template_picker::render(f, picker, f.area());
+    }
+
+    if let Some(input) = &app.date_prompt {
+        let hint = match parse_date(input, Local::now().date_naive()) {
+            Ok(date) => date.format("%A, %B %-d, %Y").to_string(),
+            Err(DateError::Empty | DateError::Unrecognized(_)) => {
+                "try yesterday, last friday, 3 days ago, 2024-05-01".to_string()
+            }
+            Err(err) => err.to_string(),
+        };
+        popup::render_input(f, "Go to date", input, &hint, f.area());
+    }
+
+    if let Some(id) = &app.confirm_delete {
+        let name = app
+            .entries
+            .iter()
+            .find(|e| e.id == *id)
+            .and_then(|e| e.title())
+            .unwrap_or_else(|| id.clone());
+        popup::render_confirm(f, "Delete entry", &format!("Move \"{}\" to the trash?", name), f.area());
     }
 }
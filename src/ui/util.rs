@@ -3,6 +3,8 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, BorderType, Borders};
 
+use crate::config::settings::{config, Theme};
+
 // Helper function to create a centered rect using up certain percentage of the available rect
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -32,13 +34,26 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 // === THEME COLORS === //
 pub const PRIMARY_COLOR: Color = Color::Rgb(121, 184, 81);     // Light green
 pub const BORDER_COLOR: Color = Color::Rgb(88, 118, 184);      // Soft blue
-pub const BACKGROUND_COLOR: Color = Color::Black;
 pub const HIGHLIGHT_COLOR: Color = Color::Rgb(255, 183, 77);   // Warm amber
-pub const TEXT_COLOR: Color = Color::White;
 pub const SECONDARY_TEXT: Color = Color::Rgb(153, 153, 153);   // Lighter gray
 pub const ACCENT_COLOR: Color = Color::Rgb(191, 97, 106);      // Soft red
 pub const SUBTLE_TEXT: Color = Color::DarkGray;
 
+// Text and background follow the configured theme so light terminals stay readable
+pub fn text_color() -> Color {
+    match config().theme {
+        Theme::Dark => Color::White,
+        Theme::Light => Color::Black,
+    }
+}
+
+pub fn background_color() -> Color {
+    match config().theme {
+        Theme::Dark => Color::Black,
+        Theme::Light => Color::White,
+    }
+}
+
 // Optional helper for consistent blocks with borders
 pub fn themed_block(title: &str) -> Block<'_> {
     Block::default()